@@ -1,10 +1,16 @@
 use std::sync::Arc;
 use async_trait::async_trait;
 use tokio::net::TcpStream;
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
+use crate::adb::enums::adb_install_method::AdbInstallMethod;
+use crate::adb::enums::adb_sync_tree_event::AdbSyncTreeEvent;
 use crate::adb::errors::adb_connection_error::AdbConnectionError;
+use crate::adb::models::adb_device_banner::AdbDeviceBanner;
+use crate::adb::models::adb_install_result::AdbInstallResult;
+use crate::adb::models::adb_pull_result::AdbPullResult;
 use crate::adb::models::adb_port_forward_info::AdbPortForwardInfo;
 use crate::adb::models::adb_port_reverse_info::AdbPortReverseInfo;
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
 
 
 #[async_trait]
@@ -12,6 +18,7 @@ pub trait AdbConnection: Send + Sync {
     async fn close(&self);
     async fn verify_connection_status(&self) -> Result<(), AdbConnectionError>;
     async fn connect(&mut self, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
+    async fn adb_await_authorization(&self, timeout_s: f64) -> Result<Option<AdbDeviceBanner>, AdbConnectionError>;
     async fn adb_disable_verity(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError>;
     async fn adb_enable_verity(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError>;
     async fn adb_reboot(&self, reboot_command: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
@@ -19,9 +26,12 @@ pub trait AdbConnection: Send + Sync {
     async fn adb_root(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError>;
     async fn adb_unroot(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError>;
     async fn adb_get_devpath(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError>;
-    async fn adb_shell_command(&self, socket: &mut TcpStream, command: String, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
-    async fn open_shell_session(&self, socket: &mut TcpStream, command: String, command_read_timeout_s: Option<f64>, input_read_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
+    async fn open_stream(&self, service: &str, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError>;
+    async fn relay_stream(&self, transaction_info: &AdbTransactionInfo, socket: &mut TcpStream, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
     async fn adb_port_forward_set(&self, adb_port_forward_info: AdbPortForwardInfo, port_forward_result_sender: oneshot::Sender<Result<(), AdbConnectionError>>, operation_timeout_s: Option<f64>);
     async fn adb_port_reverse_set(self:Arc<Self>, adb_port_reverse_info: AdbPortReverseInfo, port_reverse_result_sender: oneshot::Sender<Result<(), AdbConnectionError>>, operation_timeout_s: Option<f64>);
+    async fn adb_install(&self, apk_paths: &[String], install_args: &[String], install_method: AdbInstallMethod, operation_timeout_s: Option<f64>) -> Result<AdbInstallResult, AdbConnectionError>;
+    async fn adb_pull(&self, remote_path: &str, local_path: &str, operation_timeout_s: Option<f64>) -> Result<AdbPullResult, AdbConnectionError>;
+    async fn adb_sync_tree(&self, local_dir: &str, remote_dir: &str, event_sender: mpsc::UnboundedSender<AdbSyncTreeEvent>, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
     async fn handle_sync_mode(&self, socket: &mut TcpStream, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError>;
 }
\ No newline at end of file
@@ -1,30 +1,44 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use async_trait::async_trait;
 use tracing::error;
 use crate::adb::connections::adb_connection::AdbConnection;
 use crate::adb::errors::adb_connection_error::AdbConnectionError;
 use crate::adb::errors::adb_connection_error::AdbConnectionError::{CommunicationError, Unauthorized, UnexpectedError};
 use crate::adb::errors::adb_io_error::AdbIoError;
-use crate::constants::{ZERO};
+use crate::constants::{ADB_PROTOCOL_VERSION, ADB_VERSION_SKIP_CHECKSUM, MAX_ADB_DATA, ZERO};
 
+use crate::transport::enums::interface_type::InterfaceType;
 use crate::transport::transport::Transport;
 use crate::utils::utils::{get_adb_key_path};
 use tokio::net::{TcpStream};
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 use tokio::sync::oneshot::Sender;
+use crate::adb::enums::adb_install_method::AdbInstallMethod;
+use crate::adb::enums::adb_sync_tree_event::AdbSyncTreeEvent;
 use crate::adb::io::daemon::AdbDeviceIo;
+use crate::adb::models::adb_install_result::AdbInstallResult;
+use crate::adb::models::adb_pull_result::AdbPullResult;
+use crate::adb::models::adb_device_banner::AdbDeviceBanner;
+use crate::adb::models::adb_key_fingerprint::AdbKeyFingerprint;
 use crate::adb::models::adb_port_forward_info::AdbPortForwardInfo;
 use crate::adb::models::adb_port_reverse_info::AdbPortReverseInfo;
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
 
 
 pub struct AdbDeviceConnection {
     pub adb_keys_path: String,
     pub adb_io_manager: AdbDeviceIo,
+    interface_type: InterfaceType,
     last_local_packet_id: Mutex<u32>,
+    device_banner: OnceLock<AdbDeviceBanner>,
+    auth_key_fingerprint: Mutex<Option<AdbKeyFingerprint>>,
+    protocol_version: AtomicU32,
+    max_payload: AtomicUsize,
 }
 
 impl AdbDeviceConnection {
-    pub fn new(adb_keys_path: Option<String>, transport: Box<dyn Transport>) -> Result<Self, AdbConnectionError> {
+    pub fn new(adb_keys_path: Option<String>, transport: Box<dyn Transport>, interface_type: InterfaceType) -> Result<Self, AdbConnectionError> {
         let adb_keys_path = if let Some(path) = adb_keys_path {
             path
         } else {
@@ -34,10 +48,60 @@ impl AdbDeviceConnection {
         Ok(AdbDeviceConnection {
             adb_keys_path,
             adb_io_manager: AdbDeviceIo::new(transport),
+            interface_type,
             last_local_packet_id: Mutex::new(ZERO),
+            device_banner: OnceLock::new(),
+            auth_key_fingerprint: Mutex::new(None),
+            protocol_version: AtomicU32::new(ADB_PROTOCOL_VERSION),
+            max_payload: AtomicUsize::new(MAX_ADB_DATA as usize),
         })
     }
 
+    pub fn interface_type(&self) -> InterfaceType {
+        self.interface_type
+    }
+
+    pub fn protocol_version(&self) -> u32 {
+        self.protocol_version.load(Ordering::Relaxed)
+    }
+
+    pub fn max_payload(&self) -> usize {
+        self.max_payload.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_connection_limits(&self, device_protocol_version: u32, device_max_payload: u32) {
+        let protocol_version = device_protocol_version.min(ADB_PROTOCOL_VERSION);
+        self.protocol_version.store(protocol_version, Ordering::Relaxed);
+        if device_max_payload > ZERO {
+            self.max_payload.store(device_max_payload.min(MAX_ADB_DATA) as usize, Ordering::Relaxed);
+        }
+        self.adb_io_manager.set_skip_checksum(protocol_version >= ADB_VERSION_SKIP_CHECKSUM);
+    }
+
+    pub fn device_banner(&self) -> Option<&AdbDeviceBanner> {
+        self.device_banner.get()
+    }
+
+    pub(crate) fn set_device_banner(&self, device_banner: AdbDeviceBanner) {
+        if self.device_banner.set(device_banner).is_err() {
+            error!("Device banner was already set for this connection");
+        }
+    }
+
+    pub fn device_has_feature(&self, feature: &str) -> bool {
+        self.device_banner().is_some_and(|device_banner| device_banner.features().contains(&feature))
+    }
+
+    pub fn auth_key_fingerprint(&self) -> Option<AdbKeyFingerprint> {
+        self.auth_key_fingerprint.lock().ok().and_then(|auth_key_fingerprint| auth_key_fingerprint.clone())
+    }
+
+    pub(crate) fn set_auth_key_fingerprint(&self, auth_key_fingerprint: AdbKeyFingerprint) {
+        if let Ok(mut current_fingerprint) = self.auth_key_fingerprint.lock() {
+            *current_fingerprint = Some(auth_key_fingerprint);
+        }
+    }
+
     pub fn get_last_packet_id(&self) -> Result<u32, AdbConnectionError> {
         let mut packet_id = self.last_local_packet_id.lock().map_err(|e| UnexpectedError(e.to_string()))?;
         *packet_id += 1;
@@ -77,6 +141,10 @@ impl AdbConnection for AdbDeviceConnection {
         self.adb_connect(operation_timeout_s).await
     }
 
+    async fn adb_await_authorization(&self, timeout_s: f64) -> Result<Option<AdbDeviceBanner>, AdbConnectionError> {
+        self._adb_await_authorization(timeout_s).await
+    }
+
     async fn adb_disable_verity(&self, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError> {
         self._adb_disable_verity(operation_timeout_s).await
     }
@@ -105,12 +173,12 @@ impl AdbConnection for AdbDeviceConnection {
         unimplemented!()
     }
 
-    async fn adb_shell_command(&self, socket: &mut TcpStream, command: String, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        self._adb_shell_command(socket, command, operation_timeout_s).await
+    async fn open_stream(&self, service: &str, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError> {
+        self._open_stream(service, operation_timeout_s).await
     }
 
-    async fn open_shell_session(&self, socket: &mut TcpStream, command: String, command_read_timeout_s: Option<f64>, input_read_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        self._open_shell_session(socket, command, command_read_timeout_s, input_read_timeout_s).await
+    async fn relay_stream(&self, transaction_info: &AdbTransactionInfo, socket: &mut TcpStream, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        self._relay_stream(transaction_info, socket, operation_timeout_s).await
     }
 
     async fn adb_port_forward_set(&self, adb_port_forward_info: AdbPortForwardInfo, port_forward_result_sender: oneshot::Sender<Result<(), AdbConnectionError>>, operation_timeout_s: Option<f64>) {
@@ -121,6 +189,18 @@ impl AdbConnection for AdbDeviceConnection {
         self._adb_port_reverse_set(&adb_port_reverse_info, port_reverse_result_sender, operation_timeout_s).await;
     }
 
+    async fn adb_install(&self, apk_paths: &[String], install_args: &[String], install_method: AdbInstallMethod, operation_timeout_s: Option<f64>) -> Result<AdbInstallResult, AdbConnectionError> {
+        self._adb_install(apk_paths, install_args, install_method, operation_timeout_s).await
+    }
+
+    async fn adb_pull(&self, remote_path: &str, local_path: &str, operation_timeout_s: Option<f64>) -> Result<AdbPullResult, AdbConnectionError> {
+        self._adb_pull(remote_path, local_path, operation_timeout_s).await
+    }
+
+    async fn adb_sync_tree(&self, local_dir: &str, remote_dir: &str, event_sender: mpsc::UnboundedSender<AdbSyncTreeEvent>, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        self._adb_sync_tree(local_dir, remote_dir, event_sender, operation_timeout_s).await
+    }
+
     async fn handle_sync_mode(&self, socket: &mut TcpStream, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         match self._handle_sync_mode(socket, operation_timeout_s).await {
             Ok(_) => Ok(()),
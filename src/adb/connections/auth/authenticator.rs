@@ -1,100 +1,193 @@
 use std::fs::read_to_string;
+use std::time::Instant;
 
-use tokio_stream::StreamExt;
-use tracing::{error, info};
+use rsa::RsaPrivateKey;
+use tracing::{debug, error, info, warn};
 
 use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
 use crate::adb::errors::adb_connection_error::AdbConnectionError;
 use crate::adb::errors::adb_connection_error::AdbConnectionError::{Unauthorized, UnexpectedError};
+use crate::adb::errors::adb_io_error::AdbIoError;
+use crate::adb::models::adb_device_banner::AdbDeviceBanner;
+use crate::adb::models::adb_key_fingerprint::AdbKeyFingerprint;
 use crate::adb::models::adb_message::AdbMessage;
-use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
-use crate::constants::{ADB_PRIVATE_KEY_FILE, ADB_PUBLIC_KEY_FILE, AUTH_CODE, CNXN_CODE, ONE, OPEN_CODE, ZERO};
-use crate::transport::enums::interface_type::InterfaceType;
-use crate::utils::utils::sign_data;
+use crate::constants::{ADB_PRIVATE_KEY_FILE, ADB_PUBLIC_KEY_FILE, ADB_VENDOR_KEYS_ENV, AUTH_CODE, CNXN_CODE, ZERO};
+use crate::utils::utils::{get_adb_vendor_key_paths, load_private_key, sign_data};
 
 impl AdbDeviceConnection {
     const AUTH_TIME_OUT_SECONDS: f64 = 5.0;
+    const AUTH_TOKEN: u32 = 1;
     const AUTH_SIGNATURE: u32 = 2;
     const AUTH_RSA_PUBLIC_KEY: u32 = 3;
 
     pub async fn adb_connect(&mut self, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         info!("Initiating ADB connection");
-        let android_usb_interface = InterfaceType::android_usb();
-        self.adb_io_manager.acquire_device(android_usb_interface)
+        let interface_type = self.interface_type();
+        self.adb_io_manager.acquire_device(interface_type)
             .await.map_err(|err| AdbDeviceConnection::map_io_error(err))?;
 
         self.send_cnxn_command(operation_timeout_s).await?;
-        let cnxn_transaction_info = AdbTransactionInfo::new(ZERO, ONE);
-
-        let stream = self.read_until_auth_or_open(&cnxn_transaction_info, operation_timeout_s).await;
-        tokio::pin!(stream);
-
-        while let Some(adb_message_result) = stream.next().await {
-            return match adb_message_result {
-                Ok(adb_message_cnxn_response) => {
-                    match adb_message_cnxn_response.command() {
-                        AUTH_CODE => {
-                            info!("Authentication required");
-                            self.adb_authenticate(&adb_message_cnxn_response).await
-                                .map_err(|e| Unauthorized(e.to_string()))
-                        }
-                        OPEN_CODE => Ok(()),
-                        _ => {
-                            error!("Unknown AUTH response: cmd={} arg0={} arg1={}",
-                                  adb_message_cnxn_response.command(),
-                                  adb_message_cnxn_response.arg0(),
-                                  adb_message_cnxn_response.arg1());
-                            Err(UnexpectedError(format!(
-                                "Unknown AUTH response: {} {} {}",
-                                adb_message_cnxn_response.command(),
-                                adb_message_cnxn_response.arg0(),
-                                adb_message_cnxn_response.arg1()
-                            )))
-                        }
-                    }
-                }
-                Err(err) => {
-                    error!("Connection error: {}", err);
-                    Err(err)
-                }
-            };
+
+        let connection_timeout_s = operation_timeout_s.unwrap_or(Self::AUTH_TIME_OUT_SECONDS);
+        let adb_message_cnxn_response = self.read_connection_message(connection_timeout_s).await.map_err(|err| {
+            error!("Connection error: {}", err);
+            err
+        })?;
+
+        let adb_message_cnxn = match adb_message_cnxn_response.command() {
+            AUTH_CODE => {
+                info!("Authentication required");
+                self.adb_authenticate(&adb_message_cnxn_response).await
+                    .map_err(|e| Unauthorized(e.to_string()))?
+            }
+            CNXN_CODE => adb_message_cnxn_response,
+            _ => {
+                error!("Unknown AUTH response: cmd={} arg0={} arg1={}",
+                      adb_message_cnxn_response.command(),
+                      adb_message_cnxn_response.arg0(),
+                      adb_message_cnxn_response.arg1());
+                return Err(UnexpectedError(format!(
+                    "Unknown AUTH response: {} {} {}",
+                    adb_message_cnxn_response.command(),
+                    adb_message_cnxn_response.arg0(),
+                    adb_message_cnxn_response.arg1()
+                )));
+            }
+        };
+
+        self.complete_connection(&adb_message_cnxn);
+        Ok(())
+    }
+
+    pub async fn _adb_await_authorization(&self, timeout_s: f64) -> Result<Option<AdbDeviceBanner>, AdbConnectionError> {
+        let adb_message = match self.read_connection_message(timeout_s).await {
+            Ok(adb_message) => adb_message,
+            Err(AdbConnectionError::Timeout) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        if adb_message.command() == CNXN_CODE {
+            info!("Public key accepted by device");
+            self.complete_connection(&adb_message);
+            return Ok(self.device_banner().cloned());
         }
+        self.resend_public_key_on_token(&adb_message).await?;
+        Ok(None)
+    }
 
-        error!("Failed to establish device connection");
-        Err(UnexpectedError("Unexpected error while attempting to establish device connection".to_string()))
+    async fn resend_public_key_on_token(&self, adb_message: &AdbMessage) -> Result<(), AdbConnectionError> {
+        if adb_message.arg0() == Self::AUTH_TOKEN {
+            info!("Device requested authentication again, re-sending public key");
+            self.send_public_key().await?;
+        }
+        Ok(())
     }
 
-    async fn adb_authenticate(&self, adb_message: &AdbMessage) -> Result<(), AdbConnectionError> {
-        let private_key_path = format!("{}/{}", self.adb_keys_path, ADB_PRIVATE_KEY_FILE);
-        let public_key_path = format!("{}/{}", self.adb_keys_path, ADB_PUBLIC_KEY_FILE);
+    fn complete_connection(&self, adb_message_cnxn: &AdbMessage) {
+        self.set_connection_limits(adb_message_cnxn.arg0(), adb_message_cnxn.arg1());
+        info!("Negotiated protocol version {:#010x} with max payload {}", self.protocol_version(), self.max_payload());
 
-        let signed_token = sign_data(&private_key_path, adb_message.data()).map_err(|err| {
-            error!("Failed to sign ADB keys: {}", err);
-            Unauthorized(format!("Failed to sign ADB keys. Please verify that the key is valid and properly configured: {}", err))
-        })?;
+        let device_banner = AdbDeviceBanner::parse(adb_message_cnxn.data());
+        info!("Device connected with banner state: {}", device_banner.connection_state());
+        self.set_device_banner(device_banner);
+    }
 
-        let auth_message = AdbMessage::new(AUTH_CODE, Self::AUTH_SIGNATURE, ZERO, signed_token);
-        self.send_adb_message(&auth_message, None).await?;
+    async fn adb_authenticate(&self, adb_message: &AdbMessage) -> Result<AdbMessage, AdbConnectionError> {
+        let signing_keys = self.load_signing_keys();
+        if signing_keys.is_empty() {
+            return Err(Unauthorized(format!("No valid ADB private key found in {} or {}", self.adb_keys_path, ADB_VENDOR_KEYS_ENV)));
+        }
 
-        let adb_message_response = self.adb_io_manager.read_adb_message_last_message(Self::AUTH_TIME_OUT_SECONDS)
-            .await.map_err(|err| AdbDeviceConnection::map_io_error(err))?;
+        let mut auth_token = adb_message.data().to_vec();
+        for (key_path, private_key) in &signing_keys {
+            let signed_token = sign_data(private_key, &auth_token).map_err(|err| {
+                error!("Failed to sign ADB token with {}: {}", key_path, err);
+                Unauthorized(format!("Failed to sign ADB token with {}. Please verify that the key is valid and properly configured: {}", key_path, err))
+            })?;
+
+            let auth_message = AdbMessage::new(AUTH_CODE, Self::AUTH_SIGNATURE, ZERO, signed_token);
+            self.send_adb_message(&auth_message, None).await?;
+
+            let adb_message_response = self.read_connection_message(Self::AUTH_TIME_OUT_SECONDS).await?;
+            if adb_message_response.command() == CNXN_CODE {
+                info!("Authentication successful with {}", key_path);
+                return Ok(adb_message_response);
+            }
+            if adb_message_response.arg0() != Self::AUTH_TOKEN {
+                warn!("Unexpected AUTH type {} after signature, skipping remaining keys", adb_message_response.arg0());
+                break;
+            }
+            debug!("Signature from {} rejected by device", key_path);
+            auth_token = adb_message_response.data().to_vec();
+        }
+
+        info!("No signing key accepted by device, trying public key");
+        self.send_public_key().await?;
+        let adb_message_response = self.read_connection_message(Self::AUTH_TIME_OUT_SECONDS)
+            .await.map_err(|e| Unauthorized(e.to_string()))?;
 
         if adb_message_response.command() == CNXN_CODE {
-            info!("Authentication successful");
-            Ok(())
+            info!("Public key accepted by device");
+            Ok(adb_message_response)
         } else {
-            info!("Initial authentication failed, trying public key");
-            let string_public_key = read_to_string(&public_key_path)
-                .map_err(|err| {
-                    error!("Failed to read public ADB keys: {}", err);
-                    Unauthorized(format!("Unable to read public adb keys: {}", err))
-                })?;
-
-            let auth_message = AdbMessage::new(AUTH_CODE, Self::AUTH_RSA_PUBLIC_KEY, ZERO, string_public_key.into_bytes());
-            self.send_adb_message(&auth_message, None).await?;
-            self.adb_io_manager.read_adb_message_last_message(Self::AUTH_TIME_OUT_SECONDS)
-                .await.map_err(|e| Unauthorized(e.to_string()))?;
-            Ok(())
+            self.resend_public_key_on_token(&adb_message_response).await?;
+            Err(Unauthorized("Waiting for the public key to be accepted on the device".to_string()))
+        }
+    }
+
+    async fn send_public_key(&self) -> Result<(), AdbConnectionError> {
+        let public_key_path = format!("{}/{}", self.adb_keys_path, ADB_PUBLIC_KEY_FILE);
+        let string_public_key = read_to_string(&public_key_path)
+            .map_err(|err| {
+                error!("Failed to read public ADB keys: {}", err);
+                Unauthorized(format!("Unable to read public adb keys: {}", err))
+            })?;
+
+        match AdbKeyFingerprint::from_public_key(&public_key_path, &string_public_key) {
+            Some(auth_key_fingerprint) => {
+                info!("Offering public key {} with fingerprint MD5 {} SHA-256 {}", public_key_path, auth_key_fingerprint.md5(), auth_key_fingerprint.sha256());
+                self.set_auth_key_fingerprint(auth_key_fingerprint);
+            }
+            None => warn!("Public key {} is not in ADB format", public_key_path),
+        }
+
+        let auth_message = AdbMessage::new(AUTH_CODE, Self::AUTH_RSA_PUBLIC_KEY, ZERO, string_public_key.into_bytes());
+        self.send_adb_message(&auth_message, None).await
+    }
+
+    fn load_signing_keys(&self) -> Vec<(String, RsaPrivateKey)> {
+        let mut key_paths = vec![format!("{}/{}", self.adb_keys_path, ADB_PRIVATE_KEY_FILE)];
+        for vendor_key_path in get_adb_vendor_key_paths() {
+            if !key_paths.contains(&vendor_key_path) {
+                key_paths.push(vendor_key_path);
+            }
+        }
+
+        key_paths.into_iter()
+            .filter_map(|key_path| match load_private_key(&key_path) {
+                Ok(private_key) => Some((key_path, private_key)),
+                Err(err) => {
+                    warn!("Skipping ADB key {}: {}", key_path, err);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    async fn read_connection_message(&self, timeout_s: f64) -> Result<AdbMessage, AdbConnectionError> {
+        let start_time = Instant::now();
+        loop {
+            let remaining_timeout_s = (timeout_s - start_time.elapsed().as_secs_f64()).max(0.0);
+            match self.adb_io_manager.read_control_message(remaining_timeout_s).await {
+                Ok(adb_message) if adb_message.command() == AUTH_CODE || adb_message.command() == CNXN_CODE => {
+                    return Ok(adb_message);
+                }
+                Ok(adb_message) => {
+                    warn!("Ignoring unexpected message while connecting: cmd={}", adb_message.command());
+                }
+                Err(AdbIoError::TimeoutError) if start_time.elapsed().as_secs_f64() < timeout_s => {}
+                Err(err) => return Err(AdbDeviceConnection::map_io_error(err)),
+            }
         }
     }
 }
\ No newline at end of file
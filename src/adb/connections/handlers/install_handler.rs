@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use tokio::fs::File;
+use tracing::{info, warn};
+
+use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
+use crate::adb::enums::adb_install_method::AdbInstallMethod;
+use crate::adb::errors::adb_connection_error::AdbConnectionError;
+use crate::adb::errors::adb_connection_error::AdbConnectionError::InstallError;
+use crate::adb::models::adb_install_result::AdbInstallResult;
+use crate::constants::{ABB_EXEC_COMMAND, EXEC_COMMAND, NULL_TERMINATOR, SHELL_COMMAND};
+use crate::utils::utils::{ensure_null_terminated, shell_quote};
+
+struct AdbApkFile {
+    local_path: PathBuf,
+    split_name: String,
+    size: u64,
+}
+
+impl AdbDeviceConnection {
+    const INSTALL_TEMP_DIR: &'static str = "/data/local/tmp/";
+    const STREAM_APK_ARG: &'static str = "-";
+
+    pub async fn _adb_install(&self, apk_paths: &[String], install_args: &[String], install_method: AdbInstallMethod, operation_timeout_s: Option<f64>) -> Result<AdbInstallResult, AdbConnectionError> {
+        let apk_files = Self::read_apk_files(apk_paths).await?;
+        info!("Installing {} APK(s) using {:?}", apk_files.len(), install_method);
+
+        if install_method != AdbInstallMethod::Legacy {
+            return self.install_session(&apk_files, install_args, install_method, None, operation_timeout_s).await;
+        }
+
+        let mut remote_paths = Vec::with_capacity(apk_files.len());
+        let mut push_result = Ok(());
+        for apk_file in &apk_files {
+            let remote_path = format!("{}{}", Self::INSTALL_TEMP_DIR, apk_file.split_name);
+            push_result = self._push_local_file(&apk_file.local_path, &remote_path, operation_timeout_s).await;
+            remote_paths.push(remote_path);
+            if push_result.is_err() {
+                break;
+            }
+        }
+
+        let install_result = match push_result {
+            Ok(()) if apk_files.len() == 1 => {
+                let mut pm_args = vec!["install".to_string()];
+                pm_args.extend(install_args.iter().cloned());
+                pm_args.push(remote_paths[0].clone());
+                self.run_package_command(install_method, &pm_args, operation_timeout_s).await
+                    .map(|output| AdbInstallResult::from_output(&output))
+            }
+            Ok(()) => self.install_session(&apk_files, install_args, install_method, Some(&remote_paths), operation_timeout_s).await,
+            Err(err) => Err(err),
+        };
+
+        let remove_command = format!("{}rm -f {}", SHELL_COMMAND, remote_paths.iter().map(|path| shell_quote(path)).collect::<Vec<_>>().join(" "));
+        if let Err(err) = self.run_service(&remove_command, operation_timeout_s).await {
+            warn!("Failed to remove pushed APKs: {}", err);
+        }
+        install_result
+    }
+
+    async fn install_session(&self, apk_files: &[AdbApkFile], install_args: &[String], install_method: AdbInstallMethod, remote_paths: Option<&[String]>, operation_timeout_s: Option<f64>) -> Result<AdbInstallResult, AdbConnectionError> {
+        let total_size: u64 = apk_files.iter().map(|apk_file| apk_file.size).sum();
+        let mut create_args = vec!["install-create".to_string(), "-S".to_string(), total_size.to_string()];
+        create_args.extend(install_args.iter().cloned());
+
+        let create_output = self.run_package_command(install_method, &create_args, operation_timeout_s).await?;
+        let Some(session_id) = Self::parse_install_session_id(&create_output) else {
+            return Ok(AdbInstallResult::from_output(&create_output));
+        };
+
+        match self.write_install_session(apk_files, &session_id, install_method, remote_paths, operation_timeout_s).await {
+            Ok(write_result) if write_result.is_success() => {
+                let commit_args = vec!["install-commit".to_string(), session_id];
+                self.run_package_command(install_method, &commit_args, operation_timeout_s).await
+                    .map(|output| AdbInstallResult::from_output(&output))
+            }
+            write_result => {
+                let abandon_args = vec!["install-abandon".to_string(), session_id];
+                if let Err(err) = self.run_package_command(install_method, &abandon_args, operation_timeout_s).await {
+                    warn!("Failed to abandon install session: {}", err);
+                }
+                write_result
+            }
+        }
+    }
+
+    async fn write_install_session(&self, apk_files: &[AdbApkFile], session_id: &str, install_method: AdbInstallMethod, remote_paths: Option<&[String]>, operation_timeout_s: Option<f64>) -> Result<AdbInstallResult, AdbConnectionError> {
+        let mut write_result = AdbInstallResult::from_output("");
+        for (index, apk_file) in apk_files.iter().enumerate() {
+            let apk_source = remote_paths.map_or(Self::STREAM_APK_ARG, |remote_paths| remote_paths[index].as_str());
+            let write_args = vec![
+                "install-write".to_string(),
+                "-S".to_string(),
+                apk_file.size.to_string(),
+                session_id.to_string(),
+                apk_file.split_name.clone(),
+                apk_source.to_string(),
+            ];
+
+            let write_output = if remote_paths.is_some() {
+                self.run_package_command(install_method, &write_args, operation_timeout_s).await?
+            } else {
+                let service = Self::package_service(install_method, &write_args);
+                let local_file = File::open(&apk_file.local_path).await
+                    .map_err(|err| InstallError(format!("Failed to open {}: {}", apk_file.local_path.display(), err)))?;
+                let transaction_info = self._open_stream(&service, operation_timeout_s).await?;
+                let output = self.exchange_stream(&transaction_info, local_file, operation_timeout_s).await?;
+                String::from_utf8_lossy(&output).to_string()
+            };
+
+            write_result = AdbInstallResult::from_output(&write_output);
+            if !write_result.is_success() {
+                warn!("Failed to write {} to install session: {}", apk_file.split_name, write_result.message());
+                break;
+            }
+        }
+        Ok(write_result)
+    }
+
+    async fn run_package_command(&self, install_method: AdbInstallMethod, package_args: &[String], operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError> {
+        self.run_service(&Self::package_service(install_method, package_args), operation_timeout_s).await
+    }
+
+    fn package_service(install_method: AdbInstallMethod, package_args: &[String]) -> String {
+        let quoted_args = || package_args.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" ");
+        let service = match install_method {
+            AdbInstallMethod::AbbExec => format!("{}package{}{}", ABB_EXEC_COMMAND, NULL_TERMINATOR, package_args.join(&NULL_TERMINATOR.to_string())),
+            AdbInstallMethod::Cmd => format!("{}cmd package {}", EXEC_COMMAND, quoted_args()),
+            AdbInstallMethod::Legacy => format!("{}pm {}", SHELL_COMMAND, quoted_args()),
+        };
+        ensure_null_terminated(service)
+    }
+
+    fn parse_install_session_id(create_output: &str) -> Option<String> {
+        let (_, session_start) = create_output.split_once('[')?;
+        let (session_id, _) = session_start.split_once(']')?;
+        session_id.parse::<u32>().ok().map(|session_id| session_id.to_string())
+    }
+
+    async fn read_apk_files(apk_paths: &[String]) -> Result<Vec<AdbApkFile>, AdbConnectionError> {
+        let mut apk_files = Vec::with_capacity(apk_paths.len());
+        for (index, apk_path) in apk_paths.iter().enumerate() {
+            let local_path = Path::new(apk_path).to_path_buf();
+            let metadata = tokio::fs::metadata(&local_path).await
+                .map_err(|err| InstallError(format!("Failed to read {}: {}", apk_path, err)))?;
+            if !metadata.is_file() {
+                return Err(InstallError(format!("{} is not a file", apk_path)));
+            }
+
+            let file_name = local_path.file_name()
+                .map(|file_name| file_name.to_string_lossy().to_string())
+                .unwrap_or_else(|| format!("split_{}.apk", index));
+            apk_files.push(AdbApkFile { split_name: format!("{}_{}", index, file_name), local_path, size: metadata.len() });
+        }
+        Ok(apk_files)
+    }
+}
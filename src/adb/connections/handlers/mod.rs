@@ -1,5 +1,6 @@
-mod shell_handler;
 mod port_forward_handler;
 mod security_handler;
 mod scripting_handler;
-mod port_reverse_handler;
\ No newline at end of file
+mod port_reverse_handler;
+mod install_handler;
+mod pull_handler;
\ No newline at end of file
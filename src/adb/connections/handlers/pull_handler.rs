@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{info, warn};
+
+use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
+use crate::adb::errors::adb_connection_error::AdbConnectionError;
+use crate::adb::errors::adb_connection_error::AdbConnectionError::PullError;
+use crate::adb::models::adb_pull_result::AdbPullResult;
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
+use crate::constants::{CLSE_CODE, EXEC_COMMAND, WRTE_CODE};
+use crate::utils::utils::{ensure_null_terminated, shell_quote};
+
+impl AdbDeviceConnection {
+    const SHA256_HEX_LENGTH: usize = 64;
+    const CHECKSUM_BUFFER_SIZE: usize = 64 * 1024;
+
+    pub async fn _adb_pull(&self, remote_path: &str, local_path: &str, operation_timeout_s: Option<f64>) -> Result<AdbPullResult, AdbConnectionError> {
+        let remote_size = self.remote_file_size(remote_path, operation_timeout_s).await?;
+        let mut resume_offset = match tokio::fs::metadata(local_path).await {
+            Ok(metadata) if metadata.is_file() => metadata.len(),
+            Ok(_) => return Err(PullError(format!("{} is not a file", local_path))),
+            Err(_) => 0,
+        };
+        if resume_offset > remote_size {
+            return Err(PullError(format!("{} is larger than {} ({} > {} bytes)", local_path, remote_path, resume_offset, remote_size)));
+        }
+
+        loop {
+            let bytes_pulled = self.pull_from_offset(remote_path, local_path, resume_offset, remote_size, operation_timeout_s).await?;
+
+            let device_sha256 = self.remote_file_sha256(remote_path, operation_timeout_s).await?;
+            let local_sha256 = Self::local_file_sha256(local_path).await?;
+            if device_sha256 == local_sha256 {
+                return Ok(AdbPullResult::new(resume_offset, bytes_pulled, local_sha256));
+            }
+
+            tokio::fs::remove_file(local_path).await
+                .map_err(|err| PullError(format!("Failed to remove {} after a sha256 mismatch: {}", local_path, err)))?;
+            let mismatch_message = format!("sha256 mismatch for {}: device {} != local {}", remote_path, device_sha256, local_sha256);
+            if resume_offset == 0 {
+                return Err(PullError(mismatch_message));
+            }
+            warn!("{} after resuming from offset {}, retrying from offset 0", mismatch_message, resume_offset);
+            resume_offset = 0;
+        }
+    }
+
+    async fn pull_from_offset(&self, remote_path: &str, local_path: &str, resume_offset: u64, remote_size: u64, operation_timeout_s: Option<f64>) -> Result<u64, AdbConnectionError> {
+        let mut local_file = OpenOptions::new().create(true).append(true).open(local_path).await
+            .map_err(|err| PullError(format!("Failed to open {}: {}", local_path, err)))?;
+
+        let mut bytes_pulled = 0;
+        if resume_offset < remote_size {
+            info!("Pulling {} from offset {} of {} bytes", remote_path, resume_offset, remote_size);
+            let service = format!("{}tail -c +{} {}", EXEC_COMMAND, resume_offset + 1, shell_quote(remote_path));
+            let transaction_info = self._open_stream(&ensure_null_terminated(service), operation_timeout_s).await?;
+            bytes_pulled = self.write_stream_to_file(&transaction_info, &mut local_file, operation_timeout_s).await?;
+        }
+        local_file.sync_all().await
+            .map_err(|err| PullError(format!("Failed to write {}: {}", local_path, err)))?;
+
+        let expected_bytes = remote_size - resume_offset;
+        if bytes_pulled != expected_bytes {
+            return Err(PullError(format!("Pull of {} was truncated: received {} of {} bytes from offset {}", remote_path, bytes_pulled, expected_bytes, resume_offset)));
+        }
+        Ok(bytes_pulled)
+    }
+
+    async fn write_stream_to_file(&self, transaction_info: &AdbTransactionInfo, local_file: &mut File, operation_timeout_s: Option<f64>) -> Result<u64, AdbConnectionError> {
+        let mut bytes_written = 0;
+        loop {
+            let device_message = self.read_expected_packet(&[WRTE_CODE, CLSE_CODE], transaction_info, operation_timeout_s).await?;
+            if device_message.command() == CLSE_CODE {
+                return Ok(bytes_written);
+            }
+
+            if let Err(err) = local_file.write_all(device_message.data()).await {
+                self.send_clse_command(transaction_info, None).await?;
+                return Err(PullError(format!("Failed to write pulled data: {}", err)));
+            }
+            bytes_written += device_message.data().len() as u64;
+            self.send_okay_command(transaction_info, operation_timeout_s).await?;
+        }
+    }
+
+    async fn remote_file_size(&self, remote_path: &str, operation_timeout_s: Option<f64>) -> Result<u64, AdbConnectionError> {
+        let output = self.run_service(&format!("{}stat -L -c %s {}", EXEC_COMMAND, shell_quote(remote_path)), operation_timeout_s).await?;
+        output.trim().parse::<u64>()
+            .map_err(|_| PullError(format!("Failed to stat {}: {}", remote_path, output.trim())))
+    }
+
+    async fn remote_file_sha256(&self, remote_path: &str, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError> {
+        let output = self.run_service(&format!("{}sha256sum {}", EXEC_COMMAND, shell_quote(remote_path)), operation_timeout_s).await?;
+        output.split_whitespace().next()
+            .filter(|sha256| sha256.len() == Self::SHA256_HEX_LENGTH && sha256.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_ascii_lowercase)
+            .ok_or_else(|| PullError(format!("Failed to checksum {}: {}", remote_path, output.trim())))
+    }
+
+    async fn local_file_sha256(local_path: &str) -> Result<String, AdbConnectionError> {
+        let mut local_file = File::open(Path::new(local_path)).await
+            .map_err(|err| PullError(format!("Failed to open {}: {}", local_path, err)))?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; Self::CHECKSUM_BUFFER_SIZE];
+        loop {
+            let bytes_read = local_file.read(&mut buffer).await
+                .map_err(|err| PullError(format!("Failed to read {}: {}", local_path, err)))?;
+            if bytes_read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..bytes_read]);
+        }
+        Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+}
@@ -1,2 +1,3 @@
 mod protocol;
-mod message_stream;
\ No newline at end of file
+mod message_stream;
+mod stream_relay;
\ No newline at end of file
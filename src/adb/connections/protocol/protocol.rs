@@ -5,7 +5,7 @@ use crate::adb::errors::adb_connection_error::AdbConnectionError;
 use crate::adb::errors::adb_connection_error::AdbConnectionError::UnexpectedError;
 use crate::adb::models::adb_message::AdbMessage;
 use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
-use crate::constants::{ADB_SERVER_VERSION, CLSE_CODE, CNXN_CODE, HOST, MAX_ADB_DATA, OKAY_CODE, OPEN_CODE, WRTE_CODE, ZERO};
+use crate::constants::{ADB_PROTOCOL_VERSION, CLSE_CODE, CNXN_CODE, HOST, MAX_ADB_DATA, OKAY_CODE, OPEN_CODE, WRTE_CODE, ZERO};
 
 impl AdbDeviceConnection {
     pub async fn send_cnxn_command(&self, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
@@ -19,7 +19,7 @@ impl AdbDeviceConnection {
 
         let banner = format!("{}{}\x00", HOST, host_name).into_bytes();
 
-        let connect_message = AdbMessage::new(CNXN_CODE, ADB_SERVER_VERSION, MAX_ADB_DATA, banner);
+        let connect_message = AdbMessage::new(CNXN_CODE, ADB_PROTOCOL_VERSION, MAX_ADB_DATA, banner);
         self.send_adb_message(&connect_message, operation_timeout_s).await?;
         Ok(())
     }
@@ -27,8 +27,12 @@ impl AdbDeviceConnection {
     pub async fn send_open_command(&self, command: &str, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError> {
         let last_packet_id = self.get_last_packet_id()?;
         let transaction_info = AdbTransactionInfo::new(last_packet_id, ZERO);
+        self.adb_io_manager.register_stream(last_packet_id, ZERO).map_err(AdbDeviceConnection::map_io_error)?;
         let open_message = AdbMessage::new(OPEN_CODE, last_packet_id, ZERO, command.as_bytes().to_vec());
-        self.send_adb_message(&open_message, operation_timeout_s).await?;
+        if let Err(err) = self.send_adb_message(&open_message, operation_timeout_s).await {
+            self.adb_io_manager.unregister_stream(last_packet_id);
+            return Err(err);
+        }
         Ok(transaction_info)
     }
 
@@ -40,6 +44,7 @@ impl AdbDeviceConnection {
 
     pub async fn send_clse_command(&self, adb_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         let adb_message = AdbMessage::new(CLSE_CODE, adb_transaction_info.sent_packet_id(), adb_transaction_info.receive_packet_id(), Vec::new());
+        self.adb_io_manager.unregister_stream(adb_transaction_info.sent_packet_id());
         self.send_adb_message(&adb_message, operation_timeout_s).await
     }
 
@@ -56,15 +61,15 @@ impl AdbDeviceConnection {
     }
 
     pub async fn read_okay_response(&self, adb_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<AdbMessage, AdbConnectionError> {
-        self.read_expected_packet(&[OKAY_CODE], None, adb_transaction_info, operation_timeout_s).await
+        self.read_expected_packet(&[OKAY_CODE], adb_transaction_info, operation_timeout_s).await
     }
 
     pub async fn read_wrte_response(&self, adb_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<AdbMessage, AdbConnectionError> {
-        self.read_expected_packet(&[WRTE_CODE], None, adb_transaction_info, operation_timeout_s).await
+        self.read_expected_packet(&[WRTE_CODE], adb_transaction_info, operation_timeout_s).await
     }
 
     pub async fn read_clse_response(&self, adb_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        match self.read_expected_packet(&[CLSE_CODE], None, adb_transaction_info, operation_timeout_s).await {
+        match self.read_expected_packet(&[CLSE_CODE], adb_transaction_info, operation_timeout_s).await {
             Ok(_) => Ok(()),
             Err(AdbConnectionError::Timeout) => Ok(()),
             Err(err) => Err(err)
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use tokio::io::{empty, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::select;
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
+use crate::adb::errors::adb_connection_error::AdbConnectionError;
+use crate::adb::errors::adb_connection_error::AdbConnectionError::{CommunicationError, ConnectionCloseError};
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
+use crate::constants::{CLSE_CODE, OKAY_CODE, WRTE_CODE};
+use crate::utils::utils::ensure_null_terminated;
+
+impl AdbDeviceConnection {
+    pub async fn _open_stream(&self, service: &str, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError> {
+        let mut transaction_info = self.send_open_command(service, operation_timeout_s).await?;
+        let open_response = match self.read_expected_packet(&[OKAY_CODE, CLSE_CODE], &transaction_info, operation_timeout_s).await {
+            Ok(open_response) => open_response,
+            Err(err) => {
+                self.adb_io_manager.unregister_stream(transaction_info.sent_packet_id());
+                return Err(err);
+            }
+        };
+
+        if open_response.command() == CLSE_CODE {
+            return Err(ConnectionCloseError(format!("Device refused to open {}", service.trim_end_matches('\0'))));
+        }
+
+        transaction_info.set_receive_packet_id(open_response.arg0());
+        Ok(transaction_info)
+    }
+
+    pub async fn _relay_stream<S: AsyncRead + AsyncWrite + Unpin>(&self, transaction_info: &AdbTransactionInfo, socket: &mut S, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let Some(timeout_s) = operation_timeout_s else {
+            return self.relay_stream_until_closed(transaction_info, socket).await;
+        };
+
+        match timeout(Duration::from_secs_f64(timeout_s), self.relay_stream_until_closed(transaction_info, socket)).await {
+            Ok(relay_result) => relay_result,
+            Err(_) => {
+                debug!("Stream {} timed out after {} seconds", transaction_info.sent_packet_id(), timeout_s);
+                self.send_clse_command(transaction_info, None).await?;
+                Err(AdbConnectionError::Timeout)
+            }
+        }
+    }
+
+    pub async fn run_service(&self, service: &str, operation_timeout_s: Option<f64>) -> Result<String, AdbConnectionError> {
+        let service = ensure_null_terminated(service.to_string());
+        let transaction_info = self._open_stream(&service, operation_timeout_s).await?;
+        let output = self.read_stream_until_closed(&transaction_info, operation_timeout_s).await?;
+        Ok(String::from_utf8_lossy(&output).to_string())
+    }
+
+    pub async fn read_stream_until_closed(&self, transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<Vec<u8>, AdbConnectionError> {
+        self.exchange_stream(transaction_info, empty(), operation_timeout_s).await
+    }
+
+    pub async fn exchange_stream<R: AsyncRead + Unpin>(&self, transaction_info: &AdbTransactionInfo, mut input: R, operation_timeout_s: Option<f64>) -> Result<Vec<u8>, AdbConnectionError> {
+        let mut buffer = vec![0u8; self.max_payload()];
+        let mut output = Vec::new();
+        let mut is_input_done = false;
+        let mut is_waiting_for_okay = false;
+
+        loop {
+            if !is_input_done && !is_waiting_for_okay {
+                match input.read(&mut buffer).await {
+                    Ok(0) => is_input_done = true,
+                    Ok(bytes_read) => {
+                        self.send_wrte_command(transaction_info, &buffer[..bytes_read], operation_timeout_s).await?;
+                        is_waiting_for_okay = true;
+                    }
+                    Err(err) => {
+                        self.send_clse_command(transaction_info, None).await?;
+                        return Err(CommunicationError(format!("Failed to read stream input: {}", err)));
+                    }
+                }
+                continue;
+            }
+
+            let device_message = match self.read_expected_packet(&[], transaction_info, operation_timeout_s).await {
+                Ok(device_message) => device_message,
+                Err(err) => {
+                    if let Err(clse_err) = self.send_clse_command(transaction_info, None).await {
+                        debug!("Failed to close stream {}: {}", transaction_info.sent_packet_id(), clse_err);
+                    }
+                    return Err(err);
+                }
+            };
+
+            match device_message.command() {
+                WRTE_CODE => {
+                    output.extend_from_slice(device_message.data());
+                    self.send_okay_command(transaction_info, None).await?;
+                }
+                OKAY_CODE => is_waiting_for_okay = false,
+                CLSE_CODE => return Ok(output),
+                command => warn!("Unexpected message {:#x} on stream {}", command, transaction_info.sent_packet_id()),
+            }
+        }
+    }
+
+    async fn relay_stream_until_closed<S: AsyncRead + AsyncWrite + Unpin>(&self, transaction_info: &AdbTransactionInfo, socket: &mut S) -> Result<(), AdbConnectionError> {
+        let mut buffer = vec![0u8; self.max_payload()];
+        let mut is_waiting_for_okay = false;
+
+        loop {
+            select! {
+                read_result = socket.read(&mut buffer), if !is_waiting_for_okay => {
+                    match read_result {
+                        Ok(0) => {
+                            debug!("Client closed stream {}", transaction_info.sent_packet_id());
+                            return self.send_clse_command(transaction_info, None).await;
+                        }
+                        Ok(bytes_read) => {
+                            self.send_wrte_command(transaction_info, &buffer[..bytes_read], None).await?;
+                            is_waiting_for_okay = true;
+                        }
+                        Err(err) => {
+                            warn!("Failed to read from client stream: {}", err);
+                            return self.send_clse_command(transaction_info, None).await;
+                        }
+                    }
+                }
+                device_message = self.read_expected_packet(&[], transaction_info, None) => {
+                    let device_message = device_message?;
+                    match device_message.command() {
+                        WRTE_CODE => {
+                            if let Err(err) = socket.write_all(device_message.data()).await {
+                                self.send_clse_command(transaction_info, None).await?;
+                                return Err(CommunicationError(format!("Failed to write to client stream: {}", err)));
+                            }
+                            self.send_okay_command(transaction_info, None).await?;
+                        }
+                        OKAY_CODE => is_waiting_for_okay = false,
+                        CLSE_CODE => {
+                            debug!("Device closed stream {}", transaction_info.sent_packet_id());
+                            if let Err(err) = socket.shutdown().await {
+                                debug!("Failed to shutdown client stream: {}", err);
+                            }
+                            return Ok(());
+                        }
+                        command => warn!("Unexpected message {:#x} on stream {}", command, transaction_info.sent_packet_id()),
+                    }
+                }
+            }
+        }
+    }
+}
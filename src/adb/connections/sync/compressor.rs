@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use crate::adb::enums::adb_sync_compression::AdbSyncCompression;
+use crate::adb::errors::adb_connection_error::AdbConnectionError;
+
+pub enum SyncCompressor {
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    Lz4(lz4_flex::frame::FrameEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl SyncCompressor {
+    const BROTLI_BUFFER_SIZE: usize = 64 * 1024;
+    const BROTLI_QUALITY: u32 = 5;
+    const BROTLI_WINDOW_BITS: u32 = 22;
+    const ZSTD_LEVEL: i32 = 3;
+
+    pub fn new(compression: AdbSyncCompression) -> Result<Self, AdbConnectionError> {
+        Ok(match compression {
+            AdbSyncCompression::Brotli => SyncCompressor::Brotli(Box::new(brotli::CompressorWriter::new(Vec::new(), Self::BROTLI_BUFFER_SIZE, Self::BROTLI_QUALITY, Self::BROTLI_WINDOW_BITS))),
+            AdbSyncCompression::Lz4 => SyncCompressor::Lz4(lz4_flex::frame::FrameEncoder::new(Vec::new())),
+            AdbSyncCompression::Zstd => SyncCompressor::Zstd(zstd::stream::write::Encoder::new(Vec::new(), Self::ZSTD_LEVEL)
+                .map_err(|err| AdbConnectionError::SyncError(format!("Failed to create zstd encoder: {}", err)))?),
+        })
+    }
+
+    pub fn compress(&mut self, data: &[u8]) -> Result<Vec<u8>, AdbConnectionError> {
+        let write_result = match self {
+            SyncCompressor::Brotli(encoder) => encoder.write_all(data),
+            SyncCompressor::Lz4(encoder) => encoder.write_all(data),
+            SyncCompressor::Zstd(encoder) => encoder.write_all(data),
+        };
+        write_result.map_err(|err| AdbConnectionError::SyncError(format!("Failed to compress data: {}", err)))?;
+
+        let output = match self {
+            SyncCompressor::Brotli(encoder) => encoder.get_mut(),
+            SyncCompressor::Lz4(encoder) => encoder.get_mut(),
+            SyncCompressor::Zstd(encoder) => encoder.get_mut(),
+        };
+        Ok(std::mem::take(output))
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, AdbConnectionError> {
+        match self {
+            SyncCompressor::Brotli(encoder) => Ok(encoder.into_inner()),
+            SyncCompressor::Lz4(encoder) => encoder.finish()
+                .map_err(|err| AdbConnectionError::SyncError(format!("Failed to finish lz4 stream: {}", err))),
+            SyncCompressor::Zstd(encoder) => encoder.finish()
+                .map_err(|err| AdbConnectionError::SyncError(format!("Failed to finish zstd stream: {}", err))),
+        }
+    }
+}
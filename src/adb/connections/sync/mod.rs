@@ -1 +1,3 @@
 mod sync;
+mod compressor;
+mod sync_tree;
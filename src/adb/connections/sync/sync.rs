@@ -1,18 +1,25 @@
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
 use std::str::from_utf8;
+use std::time::UNIX_EPOCH;
 
+use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 use tokio::net::TcpStream;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
+use crate::adb::connections::sync::compressor::SyncCompressor;
 use crate::adb::enums::adb_sync_command::SyncCommand;
+use crate::adb::enums::adb_sync_compression::AdbSyncCompression;
 use crate::adb::errors::adb_connection_error::AdbConnectionError;
 use crate::adb::io::socket::{read_exact, read_string, read_u32, send_bytes};
+use crate::adb::models::adb_message::AdbMessage;
 use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
-use crate::constants::{B_FAIL, DENT_HEADER_SIZE, DENT_MIN_SIZE, OKAY, SYNC_COMMAND, SYNC_DATA_COMMAND, SYNC_DATA_COMMAND_STR, SYNC_DENT_COMMAND, SYNC_DENT_COMMAND_STR, SYNC_DONE_COMMAND, SYNC_DONE_COMMAND_STR, SYNC_LIST_COMMAND, SYNC_LIST_COMMAND_STR, SYNC_QUIT_COMMAND, SYNC_QUIT_COMMAND_STR, SYNC_RECV_COMMAND, SYNC_RECV_COMMAND_STR, SYNC_RECV_GET_DATA_TIME_SECONDS, SYNC_SEND_COMMAND, SYNC_SEND_COMMAND_STR, SYNC_STAT_COMMAND, SYNC_STAT_COMMAND_STR, ZERO};
+use crate::constants::{B_FAIL, DENT_HEADER_SIZE, DENT_NAME_LENGTH_SIZE, DENT_V2_HEADER_SIZE, LS_V2_FEATURE, OKAY, OKAY_CODE, SENDRECV_V2_DRY_RUN_SEND_FEATURE, SENDRECV_V2_FEATURE, STAT_V2_FEATURE, SYNC_COMMAND, SYNC_DATA_COMMAND, SYNC_DATA_COMMAND_STR, SYNC_DATA_MAX_SIZE, SYNC_DENT_COMMAND, SYNC_DENT_COMMAND_STR, SYNC_DENT_V2_COMMAND, SYNC_DONE_COMMAND, SYNC_DONE_COMMAND_STR, SYNC_FILE_TYPE_MASK, SYNC_FILE_TYPE_REGULAR, SYNC_FLAG_DRY_RUN, SYNC_ID_SIZE, SYNC_LIST_COMMAND, SYNC_LIST_COMMAND_STR, SYNC_LIST_V2_COMMAND, SYNC_LIST_V2_COMMAND_STR, SYNC_LSTAT_V2_COMMAND, SYNC_LSTAT_V2_COMMAND_STR, SYNC_QUIT_COMMAND, SYNC_QUIT_COMMAND_STR, SYNC_RECV_COMMAND, SYNC_RECV_COMMAND_STR, SYNC_RECV_V2_COMMAND, SYNC_RECV_V2_COMMAND_STR, SYNC_SEND_COMMAND, SYNC_SEND_COMMAND_STR, SYNC_SEND_V2_COMMAND, SYNC_SEND_V2_COMMAND_STR, SYNC_STAT_COMMAND, SYNC_STAT_COMMAND_STR, SYNC_STAT_V2_COMMAND, SYNC_STAT_V2_COMMAND_STR, WRTE_CODE, ZERO};
 
 impl AdbDeviceConnection {
-    const SYNC_MAX_CHUNK_SIZE: usize = 64 * 1024;
+    pub(super) const SYNC_STATUS_HEADER_SIZE: usize = 8;
 
     pub async fn _handle_sync_mode(&self, socket: &mut TcpStream, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         info!("Initializing sync mode");
@@ -23,15 +30,35 @@ impl AdbDeviceConnection {
             match command {
                 SyncCommand::Stat { path } => {
                     info!("Handling Stat command for path: {}", path);
-                    self.handle_stat_command(socket, &sync_command_transaction_info, &path, operation_timeout_s).await?
+                    self.handle_stat_command(socket, &sync_command_transaction_info, &path, SYNC_STAT_COMMAND, operation_timeout_s).await?
+                }
+                SyncCommand::StatV2 { path } | SyncCommand::LstatV2 { path } if !self.device_has_feature(STAT_V2_FEATURE) => {
+                    warn!("Device doesn't support {}, rejecting stat of {}", STAT_V2_FEATURE, path);
+                    Self::send_sync_fail(socket, &format!("device doesn't support {}", STAT_V2_FEATURE)).await?
+                }
+                SyncCommand::StatV2 { path } => {
+                    info!("Handling Stat v2 command for path: {}", path);
+                    self.handle_stat_command(socket, &sync_command_transaction_info, &path, SYNC_STAT_V2_COMMAND, operation_timeout_s).await?
+                }
+                SyncCommand::LstatV2 { path } => {
+                    info!("Handling Lstat v2 command for path: {}", path);
+                    self.handle_stat_command(socket, &sync_command_transaction_info, &path, SYNC_LSTAT_V2_COMMAND, operation_timeout_s).await?
                 }
                 SyncCommand::Recv { path } => {
                     info!("Handling Recv command for path: {}", path);
-                    self.handle_recv_command(socket, &sync_command_transaction_info, &path, operation_timeout_s).await?
+                    self.handle_recv_command(socket, &sync_command_transaction_info, &path, ZERO, operation_timeout_s).await?
+                }
+                SyncCommand::RecvV2 { flags, path } => {
+                    info!("Handling Recv v2 command for path: {} with flags {:#x}", path, flags);
+                    self.handle_recv_command(socket, &sync_command_transaction_info, &path, flags, operation_timeout_s).await?
                 }
                 SyncCommand::Send { mode, size: _, path } => {
                     info!("Handling Send command for path: {}", path);
-                    self.handle_send_command(socket, &sync_command_transaction_info, &path, mode, operation_timeout_s).await?
+                    self.handle_send_command(socket, &sync_command_transaction_info, &path, mode, ZERO, operation_timeout_s).await?
+                }
+                SyncCommand::SendV2 { mode, flags, path } => {
+                    info!("Handling Send v2 command for path: {} with flags {:#x}", path, flags);
+                    self.handle_send_command(socket, &sync_command_transaction_info, &path, mode, flags, operation_timeout_s).await?
                 }
                 SyncCommand::Data { .. } => {
                     error!("Received Data command without prerequisite");
@@ -43,7 +70,15 @@ impl AdbDeviceConnection {
                 }
                 SyncCommand::List { path } => {
                     info!("Handling List command for path: {}", path);
-                    self.handle_list_command(socket, &sync_command_transaction_info, &path, operation_timeout_s).await?
+                    self.handle_list_command(socket, &sync_command_transaction_info, &path, false, operation_timeout_s).await?
+                }
+                SyncCommand::ListV2 { path } if !self.device_has_feature(LS_V2_FEATURE) => {
+                    warn!("Device doesn't support {}, rejecting listing of {}", LS_V2_FEATURE, path);
+                    Self::send_sync_fail(socket, &format!("device doesn't support {}", LS_V2_FEATURE)).await?
+                }
+                SyncCommand::ListV2 { path } => {
+                    info!("Handling List v2 command for path: {}", path);
+                    self.handle_list_command(socket, &sync_command_transaction_info, &path, true, operation_timeout_s).await?
                 }
                 SyncCommand::Dent { .. } => {
                     error!("Received Dent command without prerequisite");
@@ -60,7 +95,7 @@ impl AdbDeviceConnection {
     }
 
 
-    async fn initialize_sync_mode(&self, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError> {
+    pub(super) async fn initialize_sync_mode(&self, operation_timeout_s: Option<f64>) -> Result<AdbTransactionInfo, AdbConnectionError> {
         let mut sync_command_transaction_info = self.send_open_command(SYNC_COMMAND, operation_timeout_s).await?;
         let adb_message_response = self.read_okay_response(&sync_command_transaction_info, operation_timeout_s).await?;
 
@@ -69,90 +104,315 @@ impl AdbDeviceConnection {
         Ok(sync_command_transaction_info)
     }
 
-    async fn handle_stat_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        let stat_command = Self::create_stat_command(path);
+    async fn handle_stat_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, stat_command_id: &[u8], operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let stat_command = Self::create_path_command(stat_command_id, path);
         self.send_wrte_command(sync_command_transaction_info, &stat_command, operation_timeout_s).await?;
         self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
         let write_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await?;
-        Self::handle_sync_wrte_response(write_response.data(), SYNC_STAT_COMMAND).await?;
+        Self::handle_sync_wrte_response(write_response.data(), stat_command_id).await?;
         self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
         send_bytes(socket, &write_response.data()).await.map_err(AdbDeviceConnection::map_io_error)
     }
 
-    async fn handle_recv_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        let recv_command = Self::create_recv_command(path);
+    async fn handle_recv_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, flags: u32, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        if let Some(feature) = self.unsupported_sync_flags_feature(flags) {
+            warn!("Device doesn't support {}, rejecting pull of {}", feature, path);
+            return Self::send_sync_fail(socket, &format!("device doesn't support {}", feature)).await;
+        }
+
+        let recv_command = if flags == ZERO {
+            Self::create_path_command(SYNC_RECV_COMMAND, path)
+        } else {
+            Self::create_recv_v2_command(path, flags)
+        };
         self.send_wrte_command(sync_command_transaction_info, &recv_command, operation_timeout_s).await?;
         self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
         self.recv_file_data(socket, sync_command_transaction_info, operation_timeout_s).await
     }
 
     async fn recv_file_data(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let mut record_header = Vec::with_capacity(Self::SYNC_STATUS_HEADER_SIZE);
+        let mut payload_remaining = 0;
+        let mut fail_message: Option<Vec<u8>> = None;
+
         loop {
-            let write_response = self.read_wrte_response(sync_command_transaction_info, Some(SYNC_RECV_GET_DATA_TIME_SECONDS)).await;
-            match write_response {
-                Ok(adb_message) => {
-                    send_bytes(socket, adb_message.data()).await
-                        .map_err(AdbDeviceConnection::map_io_error)?;
-                    self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+            let write_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await.inspect_err(|err| {
+                error!("Error receiving file data: {}", err);
+            })?;
+            send_bytes(socket, write_response.data()).await.map_err(AdbDeviceConnection::map_io_error)?;
+            self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+
+            let mut data: &[u8] = write_response.data();
+            while !data.is_empty() {
+                if payload_remaining > 0 {
+                    let payload_length = payload_remaining.min(data.len());
+                    if let Some(fail_message) = fail_message.as_mut() {
+                        fail_message.extend_from_slice(&data[..payload_length]);
+                    }
+                    payload_remaining -= payload_length;
+                    data = &data[payload_length..];
+                    continue;
                 }
-                Err(AdbConnectionError::Timeout) => break,
-                Err(err) => {
-                    error!("Error receiving file data: {}", err);
-                    return Err(err);
+
+                let header_length = (Self::SYNC_STATUS_HEADER_SIZE - record_header.len()).min(data.len());
+                record_header.extend_from_slice(&data[..header_length]);
+                data = &data[header_length..];
+                if record_header.len() < Self::SYNC_STATUS_HEADER_SIZE {
+                    break;
                 }
+
+                let record_length = u32::from_le_bytes([record_header[4], record_header[5], record_header[6], record_header[7]]) as usize;
+                match &record_header[..SYNC_ID_SIZE] {
+                    SYNC_DATA_COMMAND => payload_remaining = record_length,
+                    SYNC_DONE_COMMAND => return Ok(()),
+                    B_FAIL => {
+                        payload_remaining = record_length;
+                        fail_message = Some(Vec::with_capacity(record_length));
+                    }
+                    record_id => {
+                        error!("Unexpected record in file data: {:?}", record_id);
+                        return Err(AdbConnectionError::SyncError(format!("Unexpected record: {}", String::from_utf8_lossy(record_id))));
+                    }
+                }
+                record_header.clear();
+            }
+
+            if let Some(fail_message) = fail_message.as_ref().filter(|_| payload_remaining == 0) {
+                warn!("Device rejected pull: {}", String::from_utf8_lossy(fail_message));
+                return Err(AdbConnectionError::ConnectionCloseError("Device closed sync stream after a failed pull".to_string()));
             }
         }
-        Ok(())
     }
 
-    async fn handle_send_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, mode: u32, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        let init_command = Self::create_init_send_command(path, mode);
+    async fn handle_send_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, path: &str, mode: u32, flags: u32, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        if let Some(feature) = self.unsupported_sync_flags_feature(flags) {
+            warn!("Device doesn't support {}, rejecting push to {}", feature, path);
+            Self::discard_file_data(socket).await?;
+            return Self::send_sync_fail(socket, &format!("device doesn't support {}", feature)).await;
+        }
+
+        let compressor = self.init_send(sync_command_transaction_info, path, mode, flags, operation_timeout_s).await?;
+        self.send_file_data(socket, sync_command_transaction_info, compressor, operation_timeout_s).await
+    }
+
+    async fn init_send(&self, sync_command_transaction_info: &AdbTransactionInfo, path: &str, mode: u32, flags: u32, operation_timeout_s: Option<f64>) -> Result<Option<SyncCompressor>, AdbConnectionError> {
+        let is_regular_file = mode & SYNC_FILE_TYPE_MASK == SYNC_FILE_TYPE_REGULAR;
+        let compression = match AdbSyncCompression::from_flags(flags) {
+            None if is_regular_file => self.preferred_sync_compression(),
+            _ => None,
+        };
+        let send_flags = flags | compression.map_or(ZERO, |compression| compression.flag());
+
+        let init_command = if send_flags == ZERO {
+            Self::create_init_send_command(path, mode)
+        } else {
+            info!("Sending {} with flags {:#x}", path, send_flags);
+            Self::create_init_send_v2_command(path, mode, send_flags)
+        };
         self.send_wrte_command(sync_command_transaction_info, &init_command, operation_timeout_s).await?;
         self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
-        self.send_file_data(socket, sync_command_transaction_info, operation_timeout_s).await?;
-        send_bytes(socket, &OKAY.as_ref()).await.map_err(AdbDeviceConnection::map_io_error)
+        compression.map(SyncCompressor::new).transpose()
     }
 
-    async fn send_file_data(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
-        let mut buffer = Vec::new();
+    async fn send_file_data(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, mut compressor: Option<SyncCompressor>, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let mut data = Vec::new();
         loop {
-            let command = Self::read_sync_command_from_socket(socket).await?;
-
-            match command {
+            let mut records = Vec::new();
+            let is_done = match Self::read_sync_command_from_socket(socket).await? {
+                SyncCommand::Data { size } if size as usize > SYNC_DATA_MAX_SIZE => {
+                    warn!("Rejecting DATA record of {} bytes", size);
+                    Self::send_sync_fail(socket, &format!("attempted to write {} bytes, more than the {} byte maximum", size, SYNC_DATA_MAX_SIZE)).await?;
+                    if let Err(err) = self.send_clse_command(sync_command_transaction_info, None).await {
+                        debug!("Failed to close sync stream: {}", err);
+                    }
+                    return Err(AdbConnectionError::SyncError(format!("DATA record of {} bytes exceeds the {} byte maximum", size, SYNC_DATA_MAX_SIZE)));
+                }
                 SyncCommand::Data { size } => {
-                    buffer.extend_from_slice(SYNC_DATA_COMMAND);
-                    buffer.extend_from_slice(&size.to_le_bytes());
-
-                    let mut data = vec![0u8; size as usize];
+                    data.resize(size as usize, 0);
                     socket.read_exact(&mut data).await.map_err(|e| AdbConnectionError::SyncError(e.to_string()))?;
-                    buffer.extend_from_slice(&data);
+                    match compressor.as_mut() {
+                        Some(compressor) => Self::append_data_records(&mut records, &compressor.compress(&data)?),
+                        None => Self::append_data_records(&mut records, &data),
+                    }
+                    false
                 }
                 SyncCommand::Done { mtime } => {
-                    buffer.extend_from_slice(SYNC_DONE_COMMAND);
-                    buffer.extend_from_slice(&mtime.to_le_bytes());
-                    break;
+                    if let Some(compressor) = compressor.take() {
+                        Self::append_data_records(&mut records, &compressor.finish()?);
+                    }
+                    records.extend_from_slice(SYNC_DONE_COMMAND);
+                    records.extend_from_slice(&mtime.to_le_bytes());
+                    true
                 }
                 _ => {
                     error!("Expected DATA or DONE command but received something else");
                     return Err(AdbConnectionError::SyncError("Expected DATA or DONE command".to_string()));
                 }
+            };
+
+            let early_status = self.write_sync_records(sync_command_transaction_info, &records, operation_timeout_s).await?;
+            let status = match early_status {
+                Some(status) => status,
+                None if is_done => {
+                    let status_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await?;
+                    self.read_sync_status(sync_command_transaction_info, status_response, operation_timeout_s).await?
+                }
+                None => continue,
+            };
+
+            send_bytes(socket, &status).await.map_err(AdbDeviceConnection::map_io_error)?;
+            if !status.starts_with(B_FAIL) {
+                return Ok(());
             }
+
+            warn!("Device rejected push: {}", String::from_utf8_lossy(status.get(Self::SYNC_STATUS_HEADER_SIZE..).unwrap_or_default()));
+            if !is_done {
+                Self::discard_file_data(socket).await?;
+            }
+            return Err(AdbConnectionError::ConnectionCloseError("Device closed sync stream after a failed push".to_string()));
         }
+    }
 
-        for chunk in buffer.chunks(Self::SYNC_MAX_CHUNK_SIZE) {
+    async fn write_sync_records(&self, sync_command_transaction_info: &AdbTransactionInfo, records: &[u8], operation_timeout_s: Option<f64>) -> Result<Option<Vec<u8>>, AdbConnectionError> {
+        for chunk in records.chunks(self.max_payload()) {
             self.send_wrte_command(sync_command_transaction_info, chunk, operation_timeout_s).await?;
-            self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
+            let response = self.read_expected_packet(&[OKAY_CODE, WRTE_CODE], sync_command_transaction_info, operation_timeout_s).await?;
+            if response.command() == WRTE_CODE {
+                return self.read_sync_status(sync_command_transaction_info, response, operation_timeout_s).await.map(Some);
+            }
         }
-        Ok(())
+        Ok(None)
+    }
+
+    async fn read_sync_status(&self, sync_command_transaction_info: &AdbTransactionInfo, status_response: AdbMessage, operation_timeout_s: Option<f64>) -> Result<Vec<u8>, AdbConnectionError> {
+        let mut status = status_response.data().to_vec();
+        self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+
+        while !Self::is_sync_status_complete(&status) {
+            let status_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await?;
+            self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+            status.extend_from_slice(status_response.data());
+        }
+        Ok(status)
     }
 
-    async fn handle_list_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, device_path: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+    fn is_sync_status_complete(status: &[u8]) -> bool {
+        status.get(SYNC_ID_SIZE..Self::SYNC_STATUS_HEADER_SIZE)
+            .and_then(|length| length.try_into().ok())
+            .is_some_and(|length| status.len() >= Self::SYNC_STATUS_HEADER_SIZE + u32::from_le_bytes(length) as usize)
+    }
+
+    async fn discard_file_data(socket: &mut TcpStream) -> Result<(), AdbConnectionError> {
+        loop {
+            match Self::read_sync_command_from_socket(socket).await? {
+                SyncCommand::Data { size } if size as usize > SYNC_DATA_MAX_SIZE => {
+                    return Err(AdbConnectionError::SyncError(format!("DATA record of {} bytes exceeds the {} byte maximum", size, SYNC_DATA_MAX_SIZE)));
+                }
+                SyncCommand::Data { size } => {
+                    read_exact(socket, size as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                }
+                SyncCommand::Done { .. } => return Ok(()),
+                _ => return Err(AdbConnectionError::SyncError("Expected DATA or DONE command".to_string())),
+            }
+        }
+    }
+
+    fn append_data_records(buffer: &mut Vec<u8>, data: &[u8]) {
+        for data_chunk in data.chunks(SYNC_DATA_MAX_SIZE) {
+            buffer.extend_from_slice(SYNC_DATA_COMMAND);
+            buffer.extend_from_slice(&(data_chunk.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(data_chunk);
+        }
+    }
+
+    fn preferred_sync_compression(&self) -> Option<AdbSyncCompression> {
+        if !self.device_has_feature(SENDRECV_V2_FEATURE) {
+            return None;
+        }
+        AdbSyncCompression::PREFERENCE_ORDER.into_iter().find(|compression| self.device_has_feature(compression.feature()))
+    }
+
+    fn unsupported_sync_flags_feature(&self, flags: u32) -> Option<&'static str> {
+        if flags == ZERO {
+            return None;
+        }
+        if !self.device_has_feature(SENDRECV_V2_FEATURE) {
+            return Some(SENDRECV_V2_FEATURE);
+        }
+        if flags & SYNC_FLAG_DRY_RUN != 0 && !self.device_has_feature(SENDRECV_V2_DRY_RUN_SEND_FEATURE) {
+            return Some(SENDRECV_V2_DRY_RUN_SEND_FEATURE);
+        }
+        AdbSyncCompression::from_flags(flags)
+            .map(|compression| compression.feature())
+            .filter(|feature| !self.device_has_feature(feature))
+    }
+
+    pub async fn _push_local_file(&self, local_path: &Path, remote_path: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let sync_command_transaction_info = self.initialize_sync_mode(operation_timeout_s).await?;
+        self.send_local_file(&sync_command_transaction_info, local_path, remote_path, operation_timeout_s).await?;
+        self.handle_quit_command(&sync_command_transaction_info, operation_timeout_s).await
+    }
+
+    pub(super) async fn send_local_file(&self, sync_command_transaction_info: &AdbTransactionInfo, local_path: &Path, remote_path: &str, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let mut local_file = File::open(local_path).await
+            .map_err(|err| AdbConnectionError::SyncError(format!("Failed to open {}: {}", local_path.display(), err)))?;
+        let metadata = local_file.metadata().await
+            .map_err(|err| AdbConnectionError::SyncError(format!("Failed to read metadata of {}: {}", local_path.display(), err)))?;
+        let mtime = metadata.modified().ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(ZERO, |modified| modified.as_secs() as u32);
+
+        let mut compressor = self.init_send(sync_command_transaction_info, remote_path, metadata.permissions().mode(), ZERO, operation_timeout_s).await?;
+
+        let mut buffer = vec![0u8; SYNC_DATA_MAX_SIZE];
+        loop {
+            let bytes_read = local_file.read(&mut buffer).await
+                .map_err(|err| AdbConnectionError::SyncError(format!("Failed to read {}: {}", local_path.display(), err)))?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            let mut data_command = Vec::new();
+            match compressor.as_mut() {
+                Some(compressor) => Self::append_data_records(&mut data_command, &compressor.compress(&buffer[..bytes_read])?),
+                None => Self::append_data_records(&mut data_command, &buffer[..bytes_read]),
+            }
+            if let Some(status) = self.write_sync_records(sync_command_transaction_info, &data_command, operation_timeout_s).await? {
+                return Self::handle_sync_wrte_response(&status, OKAY.as_bytes()).await;
+            }
+        }
+
+        let mut done_command = Vec::new();
+        if let Some(compressor) = compressor {
+            Self::append_data_records(&mut done_command, &compressor.finish()?);
+        }
+        done_command.extend_from_slice(SYNC_DONE_COMMAND);
+        done_command.extend_from_slice(&mtime.to_le_bytes());
+
+        let status = match self.write_sync_records(sync_command_transaction_info, &done_command, operation_timeout_s).await? {
+            Some(status) => status,
+            None => {
+                let status_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await?;
+                self.read_sync_status(sync_command_transaction_info, status_response, operation_timeout_s).await?
+            }
+        };
+        Self::handle_sync_wrte_response(&status, OKAY.as_bytes()).await
+    }
+
+    async fn handle_list_command(&self, socket: &mut TcpStream, sync_command_transaction_info: &AdbTransactionInfo, device_path: &str, is_list_v2: bool, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         if device_path.is_empty() {
             error!("Empty device path provided for list command");
             return Err(AdbConnectionError::SyncError("Cannot list an empty device path".to_string()));
         }
 
-        let list_command = Self::create_list_command(device_path);
+        let (list_command_id, dent_command_id, dent_header_size) = if is_list_v2 {
+            (SYNC_LIST_V2_COMMAND, SYNC_DENT_V2_COMMAND, DENT_V2_HEADER_SIZE)
+        } else {
+            (SYNC_LIST_COMMAND, SYNC_DENT_COMMAND, DENT_HEADER_SIZE)
+        };
+        let dent_min_size = dent_header_size + DENT_NAME_LENGTH_SIZE;
+
+        let list_command = Self::create_path_command(list_command_id, device_path);
         self.send_wrte_command(sync_command_transaction_info, &list_command, operation_timeout_s).await?;
         self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
 
@@ -163,29 +423,29 @@ impl AdbDeviceConnection {
             buffer.extend_from_slice(write_response.data());
 
             let mut index = 0;
-            while index < buffer.len() {
-                if buffer.len() - index < 4 {
-                    break;
-                }
-
-                let cmd = &buffer[index..index + 4];
+            while buffer.len() - index >= SYNC_ID_SIZE {
+                let cmd = &buffer[index..index + SYNC_ID_SIZE];
 
                 match cmd {
-                    SYNC_DENT_COMMAND => {
-                        let dent_size = self.handle_dent_command(&buffer, index, socket).await?;
+                    cmd if cmd == dent_command_id => {
+                        let dent_size = Self::handle_dent_command(&buffer, index, dent_header_size, socket).await?;
                         if dent_size == 0 {
                             break;
                         }
                         index += dent_size;
                     }
                     SYNC_DONE_COMMAND => {
-                        send_bytes(socket, SYNC_DONE_COMMAND).await.map_err(AdbDeviceConnection::map_io_error)?;
+                        if buffer.len() - index < dent_min_size {
+                            break;
+                        }
+                        send_bytes(socket, &buffer[index..index + dent_min_size]).await.map_err(AdbDeviceConnection::map_io_error)?;
                         self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+                        return Ok(());
                     }
                     B_FAIL => {
-                        let error_msg = String::from_utf8_lossy(&buffer[index + 4..]).to_string();
+                        let error_msg = String::from_utf8_lossy(&buffer[index + SYNC_ID_SIZE..]).to_string();
                         error!("List command failed: {}", error_msg);
-                        send_bytes(socket, &buffer[index + 4..]).await.map_err(AdbDeviceConnection::map_io_error)?;
+                        send_bytes(socket, &buffer[index..]).await.map_err(AdbDeviceConnection::map_io_error)?;
                         return Err(AdbConnectionError::SyncError(format!("Listing failed: {}", error_msg)));
                     }
                     _ => {
@@ -199,13 +459,14 @@ impl AdbDeviceConnection {
         }
     }
 
-    async fn handle_dent_command(&self, buffer: &[u8], index: usize, socket: &mut TcpStream) -> Result<usize, AdbConnectionError> {
-        if buffer.len() - index < DENT_MIN_SIZE {
+    async fn handle_dent_command(buffer: &[u8], index: usize, dent_header_size: usize, socket: &mut TcpStream) -> Result<usize, AdbConnectionError> {
+        let dent_min_size = dent_header_size + DENT_NAME_LENGTH_SIZE;
+        if buffer.len() - index < dent_min_size {
             return Ok(ZERO as usize);
         }
 
-        let dent_size = match buffer[index + DENT_HEADER_SIZE..index + DENT_MIN_SIZE].try_into() {
-            Ok(bytes) => u32::from_le_bytes(bytes) as usize + DENT_MIN_SIZE,
+        let dent_size = match buffer[index + dent_header_size..index + dent_min_size].try_into() {
+            Ok(bytes) => u32::from_le_bytes(bytes) as usize + dent_min_size,
             Err(_) => {
                 error!("Failed to parse DENT size from buffer");
                 return Err(AdbConnectionError::SyncError("Failed to parse DENT size".to_string()));
@@ -222,7 +483,14 @@ impl AdbDeviceConnection {
         Ok(dent_size)
     }
 
-    async fn handle_quit_command(&self, sync_command_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+    async fn send_sync_fail(socket: &mut TcpStream, error_message: &str) -> Result<(), AdbConnectionError> {
+        let mut fail_response = B_FAIL.to_vec();
+        fail_response.extend_from_slice(&(error_message.len() as u32).to_le_bytes());
+        fail_response.extend_from_slice(error_message.as_bytes());
+        send_bytes(socket, &fail_response).await.map_err(AdbDeviceConnection::map_io_error)
+    }
+
+    pub(super) async fn handle_quit_command(&self, sync_command_transaction_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
         let quit_command = Self::create_quit_command();
 
         self.send_wrte_command(sync_command_transaction_info, &quit_command, operation_timeout_s).await?;
@@ -257,6 +525,14 @@ impl AdbDeviceConnection {
                     }
                 }
             }
+            SYNC_SEND_V2_COMMAND_STR => {
+                let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Self::read_sync_v2_command_id(socket, SYNC_SEND_V2_COMMAND).await?;
+                let mode = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let flags = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Ok(SyncCommand::SendV2 { mode, flags, path })
+            }
             SYNC_DATA_COMMAND_STR => {
                 let size = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
                 Ok(SyncCommand::Data { size })
@@ -275,6 +551,28 @@ impl AdbDeviceConnection {
                 let path = read_string(socket, length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
                 Ok(SyncCommand::Recv { path })
             }
+            SYNC_RECV_V2_COMMAND_STR => {
+                let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Self::read_sync_v2_command_id(socket, SYNC_RECV_V2_COMMAND).await?;
+                let flags = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Ok(SyncCommand::RecvV2 { flags, path })
+            }
+            SYNC_STAT_V2_COMMAND_STR => {
+                let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Ok(SyncCommand::StatV2 { path })
+            }
+            SYNC_LSTAT_V2_COMMAND_STR => {
+                let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Ok(SyncCommand::LstatV2 { path })
+            }
+            SYNC_LIST_V2_COMMAND_STR => {
+                let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
+                let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
+                Ok(SyncCommand::ListV2 { path })
+            }
             SYNC_LIST_COMMAND_STR => {
                 let path_length = read_u32(socket).await.map_err(AdbDeviceConnection::map_io_error)?;
                 let path = read_string(socket, path_length as usize).await.map_err(AdbDeviceConnection::map_io_error)?;
@@ -298,6 +596,15 @@ impl AdbDeviceConnection {
         }
     }
 
+    async fn read_sync_v2_command_id(socket: &mut TcpStream, expected_command_id: &[u8]) -> Result<(), AdbConnectionError> {
+        let command_id = read_exact(socket, SYNC_ID_SIZE).await.map_err(AdbDeviceConnection::map_io_error)?;
+        if command_id != expected_command_id {
+            error!("Invalid sync v2 request header: {:?}", command_id);
+            return Err(AdbConnectionError::SyncError(format!("Expected {} request header", String::from_utf8_lossy(expected_command_id))));
+        }
+        Ok(())
+    }
+
     async fn handle_sync_wrte_response(data: &[u8], expected_responses: &[u8]) -> Result<(), AdbConnectionError> {
         match data {
             d if d.starts_with(expected_responses) => {
@@ -324,26 +631,26 @@ impl AdbDeviceConnection {
         command
     }
 
-    fn create_stat_command(path: &str) -> Vec<u8> {
-        let mut stat_command = SYNC_STAT_COMMAND.to_vec();
-        stat_command.extend_from_slice(&(path.len() as u32).to_le_bytes());
-        stat_command.extend_from_slice(path.as_bytes());
-        stat_command
+    fn create_init_send_v2_command(path: &str, mode: u32, flags: u32) -> Vec<u8> {
+        let mut command = Self::create_path_command(SYNC_SEND_V2_COMMAND, path);
+        command.extend_from_slice(SYNC_SEND_V2_COMMAND);
+        command.extend_from_slice(&mode.to_le_bytes());
+        command.extend_from_slice(&flags.to_le_bytes());
+        command
     }
 
-    fn create_list_command(path: &str) -> Vec<u8> {
-        let mut stat_command = SYNC_LIST_COMMAND.to_vec();
-        stat_command.extend_from_slice(&(path.len() as u32).to_le_bytes());
-        stat_command.extend_from_slice(path.as_bytes());
-        stat_command
+    fn create_recv_v2_command(path: &str, flags: u32) -> Vec<u8> {
+        let mut command = Self::create_path_command(SYNC_RECV_V2_COMMAND, path);
+        command.extend_from_slice(SYNC_RECV_V2_COMMAND);
+        command.extend_from_slice(&flags.to_le_bytes());
+        command
     }
 
-    fn create_recv_command(path: &str) -> Vec<u8> {
-        let mut recv_command = Vec::new();
-        recv_command.extend_from_slice(SYNC_RECV_COMMAND);
-        recv_command.extend_from_slice(&(path.len() as u32).to_le_bytes());
-        recv_command.extend_from_slice(path.as_bytes());
-        recv_command
+    pub(super) fn create_path_command(command_id: &[u8], path: &str) -> Vec<u8> {
+        let mut path_command = command_id.to_vec();
+        path_command.extend_from_slice(&(path.len() as u32).to_le_bytes());
+        path_command.extend_from_slice(path.as_bytes());
+        path_command
     }
 
     fn create_quit_command() -> Vec<u8> {
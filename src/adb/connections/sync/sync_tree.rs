@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use tokio::sync::mpsc;
+use tracing::{debug, info};
+
+use crate::adb::connections::adb_device_connection::AdbDeviceConnection;
+use crate::adb::enums::adb_sync_tree_event::AdbSyncTreeEvent;
+use crate::adb::errors::adb_connection_error::AdbConnectionError;
+use crate::adb::errors::adb_connection_error::AdbConnectionError::SyncError;
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
+use crate::constants::{B_FAIL, DENT_HEADER_SIZE, DENT_NAME_LENGTH_SIZE, DENT_V2_HEADER_SIZE, LS_V2_FEATURE, SYNC_DENT_COMMAND, SYNC_DENT_V2_COMMAND, SYNC_DONE_COMMAND, SYNC_ID_SIZE, SYNC_LIST_COMMAND, SYNC_LIST_V2_COMMAND};
+
+struct AdbRemoteEntry {
+    size: u64,
+    mtime: u64,
+}
+
+struct AdbTreeFile {
+    local_path: PathBuf,
+    relative_path: String,
+    size: u64,
+}
+
+impl AdbDeviceConnection {
+    pub async fn _adb_sync_tree(&self, local_dir: &str, remote_dir: &str, event_sender: mpsc::UnboundedSender<AdbSyncTreeEvent>, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let local_root = Path::new(local_dir);
+        if !tokio::fs::metadata(local_root).await.is_ok_and(|metadata| metadata.is_dir()) {
+            return Err(SyncError(format!("{} is not a directory", local_dir)));
+        }
+        let remote_root = match remote_dir.trim_end_matches('/') {
+            "" => "/",
+            remote_root => remote_root,
+        };
+
+        let sync_command_transaction_info = self.initialize_sync_mode(operation_timeout_s).await?;
+        match self.sync_tree(&sync_command_transaction_info, local_root, remote_root, &event_sender, operation_timeout_s).await {
+            Ok(()) => self.handle_quit_command(&sync_command_transaction_info, operation_timeout_s).await,
+            Err(err) => {
+                if let Err(clse_err) = self.send_clse_command(&sync_command_transaction_info, None).await {
+                    debug!("Failed to close sync stream: {}", clse_err);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn sync_tree(&self, sync_command_transaction_info: &AdbTransactionInfo, local_root: &Path, remote_root: &str, event_sender: &mpsc::UnboundedSender<AdbSyncTreeEvent>, operation_timeout_s: Option<f64>) -> Result<(), AdbConnectionError> {
+        let is_list_v2 = self.device_has_feature(LS_V2_FEATURE);
+        let mut pending_dirs = vec![String::new()];
+        let mut changed_files = Vec::new();
+        let mut skipped_files = 0;
+
+        while let Some(relative_dir) = pending_dirs.pop() {
+            let remote_entries = self.list_remote_dir(sync_command_transaction_info, &Self::remote_tree_path(remote_root, &relative_dir), is_list_v2, operation_timeout_s).await?;
+            let local_dir = local_root.join(&relative_dir);
+            let mut dir_entries = tokio::fs::read_dir(&local_dir).await
+                .map_err(|err| SyncError(format!("Failed to read {}: {}", local_dir.display(), err)))?;
+
+            while let Some(dir_entry) = dir_entries.next_entry().await.map_err(|err| SyncError(format!("Failed to read {}: {}", local_dir.display(), err)))? {
+                let name = dir_entry.file_name().to_string_lossy().to_string();
+                let relative_path = if relative_dir.is_empty() { name.clone() } else { format!("{}/{}", relative_dir, name) };
+                let file_type = dir_entry.file_type().await
+                    .map_err(|err| SyncError(format!("Failed to read file type of {}: {}", dir_entry.path().display(), err)))?;
+                if file_type.is_symlink() {
+                    debug!("Skipping symlink {} in sync tree", dir_entry.path().display());
+                    continue;
+                }
+                let metadata = dir_entry.metadata().await
+                    .map_err(|err| SyncError(format!("Failed to read metadata of {}: {}", dir_entry.path().display(), err)))?;
+
+                if metadata.is_dir() {
+                    pending_dirs.push(relative_path);
+                    continue;
+                }
+                if !metadata.is_file() {
+                    continue;
+                }
+
+                let mtime = metadata.modified().ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |modified| modified.as_secs());
+                let is_unchanged = remote_entries.get(&name)
+                    .is_some_and(|remote_entry| remote_entry.size == metadata.len() && remote_entry.mtime == mtime);
+                if is_unchanged {
+                    skipped_files += 1;
+                } else {
+                    changed_files.push(AdbTreeFile { local_path: dir_entry.path(), relative_path, size: metadata.len() });
+                }
+            }
+        }
+
+        info!("Sync tree of {} found {} changed and {} unchanged files", local_root.display(), changed_files.len(), skipped_files);
+        let _ = event_sender.send(AdbSyncTreeEvent::Scanned { local_files: changed_files.len() + skipped_files, changed_files: changed_files.len() });
+
+        let mut pushed_bytes = 0;
+        for tree_file in &changed_files {
+            let remote_path = Self::remote_tree_path(remote_root, &tree_file.relative_path);
+            self.send_local_file(sync_command_transaction_info, &tree_file.local_path, &remote_path, operation_timeout_s).await?;
+            pushed_bytes += tree_file.size;
+            let _ = event_sender.send(AdbSyncTreeEvent::Pushed { path: tree_file.relative_path.clone(), size: tree_file.size });
+        }
+
+        let _ = event_sender.send(AdbSyncTreeEvent::Done { pushed_files: changed_files.len(), skipped_files, pushed_bytes });
+        Ok(())
+    }
+
+    async fn list_remote_dir(&self, sync_command_transaction_info: &AdbTransactionInfo, remote_dir: &str, is_list_v2: bool, operation_timeout_s: Option<f64>) -> Result<HashMap<String, AdbRemoteEntry>, AdbConnectionError> {
+        let (list_command_id, dent_command_id, dent_header_size) = if is_list_v2 {
+            (SYNC_LIST_V2_COMMAND, SYNC_DENT_V2_COMMAND, DENT_V2_HEADER_SIZE)
+        } else {
+            (SYNC_LIST_COMMAND, SYNC_DENT_COMMAND, DENT_HEADER_SIZE)
+        };
+        let dent_min_size = dent_header_size + DENT_NAME_LENGTH_SIZE;
+
+        let list_command = Self::create_path_command(list_command_id, remote_dir);
+        self.send_wrte_command(sync_command_transaction_info, &list_command, operation_timeout_s).await?;
+        self.read_okay_response(sync_command_transaction_info, operation_timeout_s).await?;
+
+        let mut remote_entries = HashMap::new();
+        let mut buffer = Vec::new();
+        loop {
+            let write_response = self.read_wrte_response(sync_command_transaction_info, operation_timeout_s).await?;
+            self.send_okay_command(sync_command_transaction_info, operation_timeout_s).await?;
+            buffer.extend_from_slice(write_response.data());
+
+            let mut index = 0;
+            while buffer.len() - index >= SYNC_ID_SIZE {
+                let record = &buffer[index..];
+                match &record[..SYNC_ID_SIZE] {
+                    SYNC_DONE_COMMAND if record.len() >= dent_min_size => return Ok(remote_entries),
+                    B_FAIL if record.len() >= Self::SYNC_STATUS_HEADER_SIZE => {
+                        let message_length = Self::read_le_u32(record, SYNC_ID_SIZE) as usize;
+                        if record.len() < Self::SYNC_STATUS_HEADER_SIZE + message_length {
+                            break;
+                        }
+                        let message = String::from_utf8_lossy(&record[Self::SYNC_STATUS_HEADER_SIZE..Self::SYNC_STATUS_HEADER_SIZE + message_length]);
+                        return Err(SyncError(format!("Failed to list {}: {}", remote_dir, message)));
+                    }
+                    record_id if record_id == dent_command_id && record.len() >= dent_min_size => {
+                        let name_length = Self::read_le_u32(record, dent_header_size) as usize;
+                        if record.len() < dent_min_size + name_length {
+                            break;
+                        }
+
+                        let name = String::from_utf8_lossy(&record[dent_min_size..dent_min_size + name_length]).to_string();
+                        let remote_entry = if is_list_v2 {
+                            (Self::read_le_u32(record, SYNC_ID_SIZE) == 0).then(|| AdbRemoteEntry { size: Self::read_le_u64(record, 40), mtime: Self::read_le_u64(record, 56) })
+                        } else {
+                            Some(AdbRemoteEntry { size: Self::read_le_u32(record, 8) as u64, mtime: Self::read_le_u32(record, 12) as u64 })
+                        };
+                        if let Some(remote_entry) = remote_entry {
+                            remote_entries.insert(name, remote_entry);
+                        }
+                        index += dent_min_size + name_length;
+                    }
+                    SYNC_DONE_COMMAND | B_FAIL => break,
+                    record_id if record_id == dent_command_id => break,
+                    record_id => return Err(SyncError(format!("Unexpected response while listing {}: {:?}", remote_dir, record_id))),
+                }
+            }
+            buffer.drain(..index);
+        }
+    }
+
+    fn remote_tree_path(remote_root: &str, relative_path: &str) -> String {
+        match (remote_root, relative_path) {
+            (remote_root, "") => remote_root.to_string(),
+            ("/", relative_path) => format!("/{}", relative_path),
+            (remote_root, relative_path) => format!("{}/{}", remote_root, relative_path),
+        }
+    }
+
+    fn read_le_u32(record: &[u8], offset: usize) -> u32 {
+        record[offset..offset + 4].try_into().map(u32::from_le_bytes).unwrap_or_default()
+    }
+
+    fn read_le_u64(record: &[u8], offset: usize) -> u64 {
+        record[offset..offset + 8].try_into().map(u64::from_le_bytes).unwrap_or_default()
+    }
+}
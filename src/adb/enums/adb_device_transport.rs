@@ -6,6 +6,7 @@ pub enum AdbDeviceTransport {
     Emulator(String),
     UsbAny,
     Usb(String),
+    Serial(String),
 }
 
 #[allow(dead_code)]
@@ -14,13 +15,17 @@ impl AdbDeviceTransport {
         AdbDeviceTransport::Usb(String::from(serial))
     }
     pub fn emulator(serial: &str) -> Self {
-        AdbDeviceTransport::Usb(String::from(serial))
+        AdbDeviceTransport::Emulator(String::from(serial))
+    }
+    pub fn serial(serial: &str) -> Self {
+        AdbDeviceTransport::Serial(String::from(serial))
     }
 
     pub fn get_serial(&self) -> Option<&String> {
         match self {
             AdbDeviceTransport::Usb(serial) => Some(serial),
             AdbDeviceTransport::Emulator(serial) => Some(serial),
+            AdbDeviceTransport::Serial(serial) => Some(serial),
             _ => None,
         }
     }
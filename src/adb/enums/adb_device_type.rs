@@ -2,6 +2,7 @@
 pub enum AdbDeviceType {
     Usb,
     Emulator,
+    Tcp,
 }
 
 impl AdbDeviceType {
@@ -12,4 +13,12 @@ impl AdbDeviceType {
     pub fn is_emulator(&self) -> bool {
         matches!(self, AdbDeviceType::Emulator)
     }
+
+    pub fn is_tcp(&self) -> bool {
+        matches!(self, AdbDeviceType::Tcp)
+    }
+
+    pub fn is_network(&self) -> bool {
+        matches!(self, AdbDeviceType::Tcp | AdbDeviceType::Emulator)
+    }
 }
\ No newline at end of file
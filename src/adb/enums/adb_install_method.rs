@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdbInstallMethod {
+    AbbExec,
+    Cmd,
+    Legacy,
+}
\ No newline at end of file
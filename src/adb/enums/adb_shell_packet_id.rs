@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdbShellPacketId {
+    Stderr = 2,
+    Exit = 3,
+}
@@ -2,11 +2,16 @@
 #[allow(dead_code)]
 pub enum SyncCommand {
     Send { mode: u32, size: u32, path: String },
+    SendV2 { mode: u32, flags: u32, path: String },
     Data { size: u32 },
     Done { mtime: u32 },
     Stat { path: String },
+    StatV2 { path: String },
+    LstatV2 { path: String },
     Recv { path: String },
+    RecvV2 { flags: u32, path: String },
     List { path: String },
+    ListV2 { path: String },
     Dent { mode: u32, size: u32, mtime: u32, name: String },
     Quit,
 }
@@ -0,0 +1,32 @@
+use crate::constants::{SENDRECV_V2_BROTLI_FEATURE, SENDRECV_V2_LZ4_FEATURE, SENDRECV_V2_ZSTD_FEATURE, SYNC_FLAG_BROTLI, SYNC_FLAG_LZ4, SYNC_FLAG_ZSTD};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdbSyncCompression {
+    Brotli,
+    Lz4,
+    Zstd,
+}
+
+impl AdbSyncCompression {
+    pub const PREFERENCE_ORDER: [AdbSyncCompression; 3] = [AdbSyncCompression::Zstd, AdbSyncCompression::Lz4, AdbSyncCompression::Brotli];
+
+    pub fn flag(&self) -> u32 {
+        match self {
+            AdbSyncCompression::Brotli => SYNC_FLAG_BROTLI,
+            AdbSyncCompression::Lz4 => SYNC_FLAG_LZ4,
+            AdbSyncCompression::Zstd => SYNC_FLAG_ZSTD,
+        }
+    }
+
+    pub fn feature(&self) -> &'static str {
+        match self {
+            AdbSyncCompression::Brotli => SENDRECV_V2_BROTLI_FEATURE,
+            AdbSyncCompression::Lz4 => SENDRECV_V2_LZ4_FEATURE,
+            AdbSyncCompression::Zstd => SENDRECV_V2_ZSTD_FEATURE,
+        }
+    }
+
+    pub fn from_flags(flags: u32) -> Option<Self> {
+        Self::PREFERENCE_ORDER.into_iter().find(|compression| flags & compression.flag() != 0)
+    }
+}
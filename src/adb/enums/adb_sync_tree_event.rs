@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdbSyncTreeEvent {
+    Scanned { local_files: usize, changed_files: usize },
+    Pushed { path: String, size: u64 },
+    Done { pushed_files: usize, skipped_files: usize, pushed_bytes: u64 },
+    Failed(String),
+}
+
+impl AdbSyncTreeEvent {
+    pub fn to_message(&self) -> String {
+        match self {
+            AdbSyncTreeEvent::Scanned { local_files, changed_files } => format!("scan {} {}", local_files, changed_files),
+            AdbSyncTreeEvent::Pushed { path, size } => format!("push {} {}", size, path),
+            AdbSyncTreeEvent::Done { pushed_files, skipped_files, pushed_bytes } => format!("done {} {} {}", pushed_files, skipped_files, pushed_bytes),
+            AdbSyncTreeEvent::Failed(message) => format!("fail {}", message),
+        }
+    }
+}
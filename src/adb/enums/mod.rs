@@ -3,4 +3,8 @@ pub mod adb_forward_type;
 pub mod adb_device_status;
 pub mod adb_device_transport;
 pub mod adb_sync_command;
-pub mod adb_device_type;
\ No newline at end of file
+pub mod adb_device_type;
+pub mod adb_shell_packet_id;
+pub mod adb_install_method;
+pub mod adb_sync_compression;
+pub mod adb_sync_tree_event;
\ No newline at end of file
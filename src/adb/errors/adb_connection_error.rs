@@ -8,6 +8,8 @@ pub enum AdbConnectionError {
     PortForwardSetupFailed(String),
     PortReverseSetupFailed(String),
     SyncError(String),
+    InstallError(String),
+    PullError(String),
     UnexpectedError(String),
 
 }
@@ -23,6 +25,8 @@ impl std::fmt::Display for AdbConnectionError {
             AdbConnectionError::PortForwardSetupFailed(msg) => write!(f, "Port forward setup failed: {}", msg),
             AdbConnectionError::PortReverseSetupFailed(msg) => write!(f, "Port reverse setup failed: {}", msg),
             AdbConnectionError::SyncError(msg) => write!(f, "Sync operation failed: {}", msg),
+            AdbConnectionError::InstallError(msg) => write!(f, "Install failed: {}", msg),
+            AdbConnectionError::PullError(msg) => write!(f, "Pull failed: {}", msg),
             AdbConnectionError::UnexpectedError(err) => write!(f, "An unexpected error occurred: {}", err),
         }
     }
@@ -1,41 +1,60 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
-use tokio::sync::Mutex;
+use tokio::select;
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
+use tracing::{error, info};
 
 use crate::adb::errors::adb_io_error::AdbIoError;
 use crate::adb::errors::adb_io_error::AdbIoError::{DeviceConnectionError, UnexpectedError};
+use crate::adb::io::message_router::AdbMessageRouter;
 use crate::adb::models::adb_message::AdbMessage;
 use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
-use crate::adb::packet_store::AdbPacketStore;
-use crate::constants::{ADB_MESSAGE_SIZE, CLSE_CODE};
+use crate::constants::{ADB_MESSAGE_SIZE, CLSE_CODE, OPEN_CODE, ZERO};
 use crate::transport::enums::interface_type::InterfaceType;
 use crate::transport::errors::transport_error::TransportError;
 use crate::transport::transport::Transport;
 
 pub struct AdbDeviceIo {
-    transport: Arc<Mutex<Box<dyn Transport>>>,
-    packet_store: Arc<AdbPacketStore>,
+    transport: Arc<RwLock<Box<dyn Transport>>>,
+    write_lock: Arc<Mutex<()>>,
+    message_router: Arc<AdbMessageRouter>,
+    skip_checksum: Arc<AtomicBool>,
+    stop_reader: watch::Sender<bool>,
 }
 
 impl AdbDeviceIo {
-    const ADB_HEADER_TIMEOUT_SECONDS: f64 = 0.05;
-    const SLEEP_BETWEEN_PACKET_GETTING_SEC: f64 = 0.5;
-    const READING_MIN_TIMEOUT: f64 = 0.0;
+    const ADB_HEADER_TIMEOUT_SECONDS: f64 = 5.0;
+    const ADB_DATA_TIMEOUT_SECONDS: f64 = 10.0;
 
     pub fn new(transport: Box<dyn Transport>) -> Self {
         Self {
-            transport: Arc::new(Mutex::new(transport)),
-            packet_store: Arc::new(AdbPacketStore::new()),
+            transport: Arc::new(RwLock::new(transport)),
+            write_lock: Arc::new(Mutex::new(())),
+            message_router: Arc::new(AdbMessageRouter::new()),
+            skip_checksum: Arc::new(AtomicBool::new(false)),
+            stop_reader: watch::channel(false).0,
         }
     }
 
+    pub fn set_skip_checksum(&self, skip_checksum: bool) {
+        self.skip_checksum.store(skip_checksum, Ordering::SeqCst);
+    }
+
     pub async fn acquire_device(&mut self, device_type: InterfaceType) -> Result<(), AdbIoError> {
-        self.transport.lock().await.acquire_device(device_type).map_err(|err| DeviceConnectionError(err.to_string()))
+        self.transport.write().await
+            .acquire_device(device_type)
+            .await
+            .map_err(|err| DeviceConnectionError(err.to_string()))?;
+        self.start_reader();
+        Ok(())
     }
 
     pub async fn release_device(&self) -> Result<(), AdbIoError> {
-        match self.transport.lock().await.release_device() {
+        self.stop_reader.send_replace(true);
+        self.message_router.close();
+        match self.transport.read().await.release_device().await {
             Ok(_) => Ok(()),
             Err(TransportError::ConnectionError(_)) => Ok(()),
             Err(err) => {
@@ -45,42 +64,154 @@ impl AdbDeviceIo {
     }
 
     pub async fn verify_connection_status(&self) -> Result<(), AdbIoError> {
-        self.transport.lock().await.verify_connection_status().map_err(|err| DeviceConnectionError(err.to_string()))
+        if self.message_router.is_closed() {
+            return Err(DeviceConnectionError("Device reader stopped".to_string()));
+        }
+        self.transport.read().await
+            .verify_connection_status()
+            .await
+            .map_err(|err| DeviceConnectionError(err.to_string()))
     }
 
     pub async fn write_bytes(&self, adb_message: &AdbMessage, transport_timeout_s: Option<f64>) -> Result<(), AdbIoError> {
-        let packed_message = adb_message.pack_message();
-        let transport = self.transport.lock().await;
+        Self::write_message(&self.transport, &self.write_lock, adb_message, !self.skip_checksum.load(Ordering::SeqCst), transport_timeout_s).await
+    }
+
+    pub fn register_stream(&self, local_id: u32, remote_id: u32) -> Result<(), AdbIoError> {
+        self.message_router.register_stream(local_id, remote_id)
+    }
+
+    pub fn unregister_stream(&self, local_id: u32) {
+        self.message_router.unregister_stream(local_id);
+    }
+
+    pub fn register_open_listener(&self, service: &str) -> Result<mpsc::Receiver<AdbMessage>, AdbIoError> {
+        self.message_router.register_open_listener(service)
+    }
+
+    pub async fn read_control_message(&self, timeout_s: f64) -> Result<AdbMessage, AdbIoError> {
+        let mut control_receiver = self.message_router.control_receiver().lock().await;
+        match tokio::time::timeout(Duration::from_secs_f64(timeout_s), control_receiver.recv()).await {
+            Ok(Some(adb_message)) => Ok(adb_message),
+            Ok(None) => Err(AdbIoError::ConnectionClosed("Device connection is closed".to_string())),
+            Err(_) if self.message_router.is_closed() => Err(AdbIoError::ConnectionClosed("Device connection is closed".to_string())),
+            Err(_) => Err(AdbIoError::TimeoutError),
+        }
+    }
+
+    pub async fn read_stream_message(&self, expected_cmds: &[u32], adb_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<AdbMessage, AdbIoError> {
+        let stream_queue = self.message_router.stream_queue(adb_info.sent_packet_id())?;
+        let mut stream_queue = stream_queue.lock().await;
+        let is_expected = |adb_message: &AdbMessage| {
+            expected_cmds.is_empty() || expected_cmds.contains(&adb_message.command())
+        };
+
+        let adb_message = match stream_queue.take_pending(is_expected) {
+            Some(adb_message) => adb_message,
+            None => {
+                let read_expected = async {
+                    loop {
+                        match stream_queue.recv().await {
+                            Some(adb_message) if is_expected(&adb_message) => return Ok(adb_message),
+                            Some(adb_message) => stream_queue.push_pending(adb_message),
+                            None => return Err(AdbIoError::ConnectionClosed("Device stream is closed".to_string())),
+                        }
+                    }
+                };
+
+                match operation_timeout_s {
+                    Some(timeout_s) => tokio::time::timeout(Duration::from_secs_f64(timeout_s), read_expected)
+                        .await
+                        .map_err(|_| AdbIoError::TimeoutError)??,
+                    None => read_expected.await?,
+                }
+            }
+        };
+
+        if adb_message.command() == CLSE_CODE {
+            self.message_router.unregister_stream(adb_info.sent_packet_id());
+        }
+        Ok(adb_message)
+    }
+
+    fn start_reader(&self) {
+        let transport = self.transport.clone();
+        let write_lock = self.write_lock.clone();
+        let message_router = self.message_router.clone();
+        let skip_checksum = self.skip_checksum.clone();
+        let mut stop_reader = self.stop_reader.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let read_result = select! {
+                    read_result = async { Self::read_message(transport.read().await.as_ref(), skip_checksum.load(Ordering::SeqCst)).await } => read_result,
+                    _ = stop_reader.wait_for(|is_stopped| *is_stopped) => break,
+                };
+
+                match read_result {
+                    Ok(adb_message) => {
+                        let (command, remote_id) = (adb_message.command(), adb_message.arg0());
+                        if !message_router.route_message(adb_message).await && command == OPEN_CODE {
+                            let close_message = AdbMessage::new(CLSE_CODE, ZERO, remote_id, Vec::new());
+                            if let Err(err) = Self::write_message(&transport, &write_lock, &close_message, !skip_checksum.load(Ordering::SeqCst), None).await {
+                                error!("Failed to reject device stream: {}", err);
+                            }
+                        }
+                    }
+                    Err(AdbIoError::TimeoutError) => {}
+                    Err(err) => {
+                        if !*stop_reader.borrow() {
+                            error!("Device reader stopped: {}", err);
+                        }
+                        break;
+                    }
+                }
+            }
+
+            info!("Device reader finished");
+            message_router.close();
+        });
+    }
+
+    async fn write_message(transport: &RwLock<Box<dyn Transport>>, write_lock: &Mutex<()>, adb_message: &AdbMessage, include_checksum: bool, transport_timeout_s: Option<f64>) -> Result<(), AdbIoError> {
+        let packed_message = adb_message.pack_message(include_checksum);
+        let transport = transport.read().await;
+        let _write_guard = write_lock.lock().await;
 
         transport.bulk_write(&packed_message, transport_timeout_s)
+            .await
             .map_err(Self::map_transport_error)?;
 
         if !adb_message.data().is_empty() {
             transport.bulk_write(adb_message.data(), transport_timeout_s)
+                .await
                 .map_err(Self::map_transport_error)?;
         }
 
         Ok(())
     }
 
-    pub async fn read_adb_message_last_message(&self, data_timeout_s: f64) -> Result<AdbMessage, AdbIoError> {
-        let transport = self.transport.lock().await;
-        let (header_msg, data_length, data_checksum) = self.read_message_header(&transport, Self::ADB_HEADER_TIMEOUT_SECONDS).await?;
+    async fn read_message(transport: &dyn Transport, skip_checksum: bool) -> Result<AdbMessage, AdbIoError> {
+        let (header_msg, data_length, data_checksum) = Self::read_message_header(transport, Self::ADB_HEADER_TIMEOUT_SECONDS).await?;
 
         if data_length == 0 {
             return Ok(header_msg);
         }
 
-        self.read_message_data(&transport, header_msg, data_length, data_checksum, data_timeout_s).await
+        Self::read_message_data(transport, header_msg, data_length, data_checksum, skip_checksum).await
     }
 
-    async fn read_message_data(&self, transport: &Box<dyn Transport>, mut header_msg: AdbMessage, data_length: u32, data_checksum: u32, timeout_s: f64) -> Result<AdbMessage, AdbIoError> {
-        let message_data = transport.bulk_read(data_length as usize, timeout_s)
-            .map_err(Self::map_transport_error)?;
+    async fn read_message_data(transport: &dyn Transport, mut header_msg: AdbMessage, data_length: u32, data_checksum: u32, skip_checksum: bool) -> Result<AdbMessage, AdbIoError> {
+        let message_data = transport.bulk_read(data_length as usize, Self::ADB_DATA_TIMEOUT_SECONDS)
+            .await
+            .map_err(|err| match err {
+                TransportError::Timeout => AdbIoError::CommunicationError("Timed out reading message data".to_string()),
+                err => Self::map_transport_error(err),
+            })?;
 
         header_msg.set_data(message_data);
 
-        if header_msg.checksum() != data_checksum {
+        if !skip_checksum && header_msg.checksum() != data_checksum {
             return Err(UnexpectedError(
                 format!("Checksum mismatch: received {} != expected {}",
                         header_msg.checksum(), data_checksum)
@@ -90,8 +221,9 @@ impl AdbDeviceIo {
         Ok(header_msg)
     }
 
-    async fn read_message_header(&self, transport: &Box<dyn Transport>, timeout_s: f64) -> Result<(AdbMessage, u32, u32), AdbIoError> {
+    async fn read_message_header(transport: &dyn Transport, timeout_s: f64) -> Result<(AdbMessage, u32, u32), AdbIoError> {
         let bytes_response = transport.bulk_read(ADB_MESSAGE_SIZE, timeout_s)
+            .await
             .map_err(Self::map_transport_error)?;
 
         let (command, arg0, arg1, data_length, data_checksum) = AdbMessage::unpack_message(&bytes_response)
@@ -101,81 +233,6 @@ impl AdbDeviceIo {
         Ok((header_msg, data_length, data_checksum))
     }
 
-    pub async fn read_adb_message_with_packet_store(&self, expected_cmds: &[u32], excepted_data: Option<String>, adb_info: &AdbTransactionInfo, operation_timeout_s: Option<f64>) -> Result<AdbMessage, AdbIoError> {
-        let excepted_data = excepted_data.as_ref();
-        let start_time = std::time::Instant::now();
-
-        loop {
-            if let Some(timeout) = operation_timeout_s {
-                if start_time.elapsed().as_secs_f64() > timeout {
-                    return Err(AdbIoError::TimeoutError);
-                }
-            }
-
-            {
-                if let Some((cmd, arg0, arg1, data)) = self.packet_store.get_packet(adb_info.receive_packet_id(), adb_info.sent_packet_id(), expected_cmds) {
-                    if (expected_cmds.contains(&cmd) || expected_cmds.is_empty()) &&
-                        (excepted_data.map_or(true, |expected| expected.as_bytes() == data)) {
-                        if cmd == CLSE_CODE {
-                            self.packet_store.clear_packet(arg0, arg1);
-                        }
-                        return Ok(AdbMessage::new(cmd, arg0, arg1, data));
-                    } else {
-                        self.packet_store.put_packet(arg0, arg1, cmd, data.clone());
-                    }
-                }
-            }
-
-            let remaining_timeout = if let Some(timeout) = operation_timeout_s {
-                (timeout - start_time.elapsed().as_secs_f64()).max(Self::READING_MIN_TIMEOUT)
-            } else {
-                Self::READING_MIN_TIMEOUT
-            };
-
-            let adb_message = match self.read_adb_message_last_message(remaining_timeout).await {
-                Ok(adb_message) => {
-                    adb_message
-                }
-                Err(AdbIoError::TimeoutError) => {
-                    if let Some(timeout) = operation_timeout_s {
-                        let elapsed = start_time.elapsed().as_secs_f64();
-                        if elapsed + Self::SLEEP_BETWEEN_PACKET_GETTING_SEC <= timeout {
-                            tokio::time::sleep(Duration::from_secs_f64(Self::SLEEP_BETWEEN_PACKET_GETTING_SEC)).await;
-                        }
-                    } else {
-                        tokio::time::sleep(Duration::from_secs_f64(Self::SLEEP_BETWEEN_PACKET_GETTING_SEC)).await;
-                    }
-                    continue;
-                }
-                Err(err) => {
-                    return Err(err);
-                }
-            };
-
-            if !adb_info.args_match(adb_message.arg0(), adb_message.arg1()) {
-                self.packet_store.put_packet(
-                    adb_message.arg0(),
-                    adb_message.arg1(),
-                    adb_message.command(),
-                    adb_message.data().clone(),
-                );
-            } else if expected_cmds.contains(&adb_message.command()) || expected_cmds.is_empty() &&
-                (excepted_data.map_or(true, |data| data.as_bytes() == adb_message.data())) {
-                if adb_message.command() == CLSE_CODE {
-                    self.packet_store.clear_packet(adb_message.arg0(), adb_message.arg1());
-                }
-                return Ok(adb_message);
-            } else {
-                self.packet_store.put_packet(
-                    adb_message.arg0(),
-                    adb_message.arg1(),
-                    adb_message.command(),
-                    adb_message.data().clone(),
-                );
-            }
-        }
-    }
-
     fn map_transport_error(err: TransportError) -> AdbIoError {
         match err {
             TransportError::Timeout => AdbIoError::TimeoutError,
@@ -188,4 +245,9 @@ impl AdbDeviceIo {
     }
 }
 
-
+impl Drop for AdbDeviceIo {
+    fn drop(&mut self) {
+        self.stop_reader.send_replace(true);
+        self.message_router.close();
+    }
+}
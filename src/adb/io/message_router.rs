@@ -0,0 +1,183 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, warn};
+
+use crate::adb::errors::adb_io_error::AdbIoError;
+use crate::adb::models::adb_message::AdbMessage;
+use crate::constants::{AUTH_CODE, CNXN_CODE, NULL_TERMINATOR, OPEN_CODE, ZERO};
+
+pub struct AdbStreamQueue {
+    receiver: mpsc::Receiver<AdbMessage>,
+    pending: VecDeque<AdbMessage>,
+}
+
+impl AdbStreamQueue {
+    pub fn take_pending(&mut self, is_expected: impl Fn(&AdbMessage) -> bool) -> Option<AdbMessage> {
+        let position = self.pending.iter().position(is_expected)?;
+        self.pending.remove(position)
+    }
+
+    pub fn push_pending(&mut self, adb_message: AdbMessage) {
+        self.pending.push_back(adb_message);
+    }
+
+    pub async fn recv(&mut self) -> Option<AdbMessage> {
+        self.receiver.recv().await
+    }
+}
+
+struct AdbStreamChannel {
+    remote_id: AtomicU32,
+    sender: mpsc::Sender<AdbMessage>,
+    queue: Arc<Mutex<AdbStreamQueue>>,
+}
+
+pub struct AdbMessageRouter {
+    streams: DashMap<u32, AdbStreamChannel>,
+    open_listeners: DashMap<String, mpsc::Sender<AdbMessage>>,
+    control_sender: mpsc::Sender<AdbMessage>,
+    control_receiver: Mutex<mpsc::Receiver<AdbMessage>>,
+    is_closed: AtomicBool,
+}
+
+impl AdbMessageRouter {
+    const CONTROL_CHANNEL_CAPACITY: usize = 8;
+    const OPEN_LISTENER_CHANNEL_CAPACITY: usize = 16;
+    const STREAM_CHANNEL_CAPACITY: usize = 32;
+    const STREAM_SEND_TIMEOUT_SECONDS: u64 = 10;
+
+    pub fn new() -> Self {
+        let (control_sender, control_receiver) = mpsc::channel(Self::CONTROL_CHANNEL_CAPACITY);
+        Self {
+            streams: DashMap::new(),
+            open_listeners: DashMap::new(),
+            control_sender,
+            control_receiver: Mutex::new(control_receiver),
+            is_closed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn register_stream(&self, local_id: u32, remote_id: u32) -> Result<(), AdbIoError> {
+        self.ensure_open()?;
+        let (sender, receiver) = mpsc::channel(Self::STREAM_CHANNEL_CAPACITY);
+        let queue = Arc::new(Mutex::new(AdbStreamQueue { receiver, pending: VecDeque::new() }));
+        self.streams.insert(local_id, AdbStreamChannel { remote_id: AtomicU32::new(remote_id), sender, queue });
+        Ok(())
+    }
+
+    pub fn unregister_stream(&self, local_id: u32) {
+        self.streams.remove(&local_id);
+    }
+
+    pub fn stream_queue(&self, local_id: u32) -> Result<Arc<Mutex<AdbStreamQueue>>, AdbIoError> {
+        match self.streams.get(&local_id) {
+            Some(stream_channel) => Ok(stream_channel.queue.clone()),
+            None => {
+                self.ensure_open()?;
+                Err(AdbIoError::ConnectionClosed(format!("Stream {} is not open", local_id)))
+            }
+        }
+    }
+
+    pub fn register_open_listener(&self, service: &str) -> Result<mpsc::Receiver<AdbMessage>, AdbIoError> {
+        self.ensure_open()?;
+        let (sender, receiver) = mpsc::channel(Self::OPEN_LISTENER_CHANNEL_CAPACITY);
+        self.open_listeners.insert(Self::normalize_service(service.as_bytes()), sender);
+        Ok(receiver)
+    }
+
+    pub fn control_receiver(&self) -> &Mutex<mpsc::Receiver<AdbMessage>> {
+        &self.control_receiver
+    }
+
+    pub async fn route_message(&self, adb_message: AdbMessage) -> bool {
+        match adb_message.command() {
+            CNXN_CODE | AUTH_CODE => {
+                if let Err(err) = self.control_sender.try_send(adb_message) {
+                    warn!("Dropping control message, queue is full or closed: {}", err);
+                }
+                true
+            }
+            OPEN_CODE => {
+                let service = Self::normalize_service(adb_message.data());
+                let send_result = match self.open_listeners.get(&service) {
+                    Some(listener) => listener.try_send(adb_message),
+                    None => {
+                        warn!("No listener for device stream open request: {}", service);
+                        return false;
+                    }
+                };
+                match send_result {
+                    Ok(()) => true,
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        warn!("Rejecting device stream open request for {}, too many pending requests", service);
+                        false
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => {
+                        warn!("No listener for device stream open request: {}", service);
+                        self.open_listeners.remove(&service);
+                        false
+                    }
+                }
+            }
+            _ => self.route_stream_message(adb_message).await,
+        }
+    }
+
+    async fn route_stream_message(&self, adb_message: AdbMessage) -> bool {
+        let (local_id, remote_id) = (adb_message.arg1(), adb_message.arg0());
+        let sender = match self.streams.get(&local_id) {
+            Some(stream_channel) => {
+                let bound_remote_id = match stream_channel.remote_id.compare_exchange(ZERO, remote_id, Ordering::SeqCst, Ordering::SeqCst) {
+                    Ok(_) => remote_id,
+                    Err(bound_remote_id) => bound_remote_id,
+                };
+                if remote_id != bound_remote_id {
+                    warn!("Dropping message {:#x} for stream {} from remote {}, expected remote {}", adb_message.command(), local_id, remote_id, bound_remote_id);
+                    return true;
+                }
+                stream_channel.sender.clone()
+            }
+            None => {
+                debug!("Dropping message {:#x} for closed stream {}", adb_message.command(), local_id);
+                return true;
+            }
+        };
+
+        match tokio::time::timeout(Duration::from_secs(Self::STREAM_SEND_TIMEOUT_SECONDS), sender.send(adb_message)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => debug!("Stream {} was closed before its message was delivered", local_id),
+            Err(_) => {
+                warn!("Stream {} is not draining its messages, closing it", local_id);
+                self.streams.remove(&local_id);
+            }
+        }
+        true
+    }
+
+    pub fn close(&self) {
+        self.is_closed.store(true, Ordering::SeqCst);
+        self.streams.clear();
+        self.open_listeners.clear();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+
+    fn ensure_open(&self) -> Result<(), AdbIoError> {
+        if self.is_closed() {
+            return Err(AdbIoError::ConnectionClosed("Device connection is closed".to_string()));
+        }
+        Ok(())
+    }
+
+    fn normalize_service(service: &[u8]) -> String {
+        String::from_utf8_lossy(service).trim_end_matches(NULL_TERMINATOR).to_string()
+    }
+}
@@ -1,2 +1,3 @@
 pub mod daemon;
+pub mod message_router;
 pub mod socket;
@@ -3,5 +3,4 @@ pub mod enums;
 pub mod errors;
 pub mod io;
 pub mod models;
-pub mod packet_store;
 pub mod server;
\ No newline at end of file
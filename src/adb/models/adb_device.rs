@@ -5,8 +5,11 @@ use crate::adb::enums::adb_device_status::AdbDeviceStatus;
 use crate::adb::enums::adb_device_type::AdbDeviceType;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use dashmap::DashMap;
+use crate::adb::models::adb_device_banner::AdbDeviceBanner;
+use crate::adb::models::adb_key_fingerprint::AdbKeyFingerprint;
 use crate::adb::models::adb_port_forward::AdbPortForward;
 use crate::adb::models::adb_port_reverse::AdbPortReverse;
+use crate::constants::ADB_SERVER_FEATURES;
 
 
 pub struct AdbDevice {
@@ -18,6 +21,10 @@ pub struct AdbDevice {
     adb_ports_reverse_hs: DashMap<String, AdbPortReverse>,
     last_monitored_at: AtomicU64,
     monitoring_interval: Duration,
+    device_path: Option<String>,
+    device_banner: Option<AdbDeviceBanner>,
+    auth_key_fingerprint: Option<AdbKeyFingerprint>,
+    transport_id: u64,
 }
 
 #[allow(dead_code)]
@@ -32,6 +39,10 @@ impl AdbDevice {
             adb_ports_reverse_hs: DashMap::new(),
             last_monitored_at: AtomicU64::new(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()),
             monitoring_interval,
+            device_path: None,
+            device_banner: None,
+            auth_key_fingerprint: None,
+            transport_id: 0,
         }
     }
 
@@ -55,6 +66,46 @@ impl AdbDevice {
         self.adb_device_status = device_status;
     }
 
+    pub fn set_device_details(&mut self, device_path: Option<String>, device_banner: Option<AdbDeviceBanner>, transport_id: u64) {
+        self.device_path = device_path;
+        self.device_banner = device_banner;
+        self.transport_id = transport_id;
+    }
+
+    pub fn device_path(&self) -> Option<&str> {
+        self.device_path.as_deref()
+    }
+
+    pub fn device_banner(&self) -> Option<&AdbDeviceBanner> {
+        self.device_banner.as_ref()
+    }
+
+    pub fn auth_key_fingerprint(&self) -> Option<&AdbKeyFingerprint> {
+        self.auth_key_fingerprint.as_ref()
+    }
+
+    pub fn set_auth_key_fingerprint(&mut self, auth_key_fingerprint: Option<AdbKeyFingerprint>) {
+        self.auth_key_fingerprint = auth_key_fingerprint;
+    }
+
+    pub fn supported_features(&self) -> Vec<&str> {
+        match &self.device_banner {
+            Some(device_banner) => device_banner.features()
+                .into_iter()
+                .filter(|feature| ADB_SERVER_FEATURES.contains(feature))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.supported_features().contains(&feature)
+    }
+
+    pub fn transport_id(&self) -> u64 {
+        self.transport_id
+    }
+
 
     pub fn is_emulator_device(&self) -> bool {
         self.adb_device_type.is_emulator()
@@ -64,6 +115,10 @@ impl AdbDevice {
         self.adb_device_type.is_usb()
     }
 
+    pub fn is_tcp_device(&self) -> bool {
+        self.adb_device_type.is_tcp()
+    }
+
     pub fn is_monitoring_interval_passed(&self) -> bool {
         let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
         let last_monitored = self.last_monitored_at.load(Ordering::Relaxed);
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct AdbDeviceBanner {
+    connection_state: String,
+    properties: HashMap<String, String>,
+}
+
+impl AdbDeviceBanner {
+    const STATE_SEPARATOR: &'static str = "::";
+    const PROPERTIES_SEPARATOR: char = ';';
+    const PROPERTY_VALUE_SEPARATOR: char = '=';
+    const PRODUCT_NAME_PROPERTY: &'static str = "ro.product.name";
+    const PRODUCT_MODEL_PROPERTY: &'static str = "ro.product.model";
+    const PRODUCT_DEVICE_PROPERTY: &'static str = "ro.product.device";
+    const FEATURES_PROPERTY: &'static str = "features";
+    const FEATURES_SEPARATOR: char = ',';
+
+    pub fn parse(banner_data: &[u8]) -> Self {
+        let banner = String::from_utf8_lossy(banner_data);
+        let banner = banner.trim_end_matches('\0');
+
+        let (connection_state, properties_str) = match banner.split_once(Self::STATE_SEPARATOR) {
+            Some((state, properties)) => (state.to_string(), properties),
+            None => (banner.split(':').next().unwrap_or_default().to_string(), ""),
+        };
+
+        let properties = properties_str
+            .split(Self::PROPERTIES_SEPARATOR)
+            .filter_map(|property| property.split_once(Self::PROPERTY_VALUE_SEPARATOR))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        AdbDeviceBanner { connection_state, properties }
+    }
+
+    pub fn connection_state(&self) -> &str {
+        &self.connection_state
+    }
+
+    pub fn product(&self) -> Option<&str> {
+        self.property(Self::PRODUCT_NAME_PROPERTY)
+    }
+
+    pub fn model(&self) -> Option<&str> {
+        self.property(Self::PRODUCT_MODEL_PROPERTY)
+    }
+
+    pub fn device(&self) -> Option<&str> {
+        self.property(Self::PRODUCT_DEVICE_PROPERTY)
+    }
+
+    pub fn features(&self) -> Vec<&str> {
+        self.property(Self::FEATURES_PROPERTY)
+            .map(|features| features.split(Self::FEATURES_SEPARATOR).filter(|feature| !feature.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(String::as_str)
+    }
+}
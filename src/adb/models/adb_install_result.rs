@@ -0,0 +1,32 @@
+#[derive(Debug, Clone)]
+pub struct AdbInstallResult {
+    is_success: bool,
+    message: String,
+}
+
+impl AdbInstallResult {
+    const SUCCESS_PREFIX: &'static str = "Success";
+    const FAILURE_PREFIX: &'static str = "Failure";
+
+    pub fn from_output(output: &str) -> Self {
+        let lines: Vec<&str> = output.lines().map(str::trim).collect();
+        if let Some(success_line) = lines.iter().find(|line| line.starts_with(Self::SUCCESS_PREFIX)) {
+            return AdbInstallResult { is_success: true, message: success_line.to_string() };
+        }
+
+        let message = match lines.iter().find(|line| line.starts_with(Self::FAILURE_PREFIX)) {
+            Some(failure_line) => failure_line.to_string(),
+            None if output.trim().is_empty() => format!("{} [UNKNOWN]", Self::FAILURE_PREFIX),
+            None => format!("{} [{}]", Self::FAILURE_PREFIX, output.trim()),
+        };
+        AdbInstallResult { is_success: false, message }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.is_success
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,39 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone)]
+pub struct AdbKeyFingerprint {
+    public_key_path: String,
+    md5: String,
+    sha256: String,
+}
+
+impl AdbKeyFingerprint {
+    pub fn from_public_key(public_key_path: &str, public_key: &str) -> Option<Self> {
+        let encoded_key = public_key.split_whitespace().next()?;
+        let key_bytes = STANDARD.decode(encoded_key).ok()?;
+        Some(AdbKeyFingerprint {
+            public_key_path: public_key_path.to_string(),
+            md5: Self::format_digest(&Md5::digest(&key_bytes)),
+            sha256: Self::format_digest(&Sha256::digest(&key_bytes)),
+        })
+    }
+
+    pub fn public_key_path(&self) -> &str {
+        &self.public_key_path
+    }
+
+    pub fn md5(&self) -> &str {
+        &self.md5
+    }
+
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    fn format_digest(digest: &[u8]) -> String {
+        digest.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<String>>().join(":")
+    }
+}
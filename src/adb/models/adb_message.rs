@@ -29,13 +29,14 @@ impl AdbMessage {
         adb_message
     }
 
-    pub fn pack_message(&self) -> Vec<u8> {
+    pub fn pack_message(&self, include_checksum: bool) -> Vec<u8> {
+        let checksum = if include_checksum { self.checksum() } else { 0 };
         let mut message = Vec::new();
         message.write_u32::<LittleEndian>(self.command).unwrap();
         message.write_u32::<LittleEndian>(self.arg0).unwrap();
         message.write_u32::<LittleEndian>(self.arg1).unwrap();
         message.write_u32::<LittleEndian>(self.data.len() as u32).unwrap();
-        message.write_u32::<LittleEndian>(self.checksum()).unwrap();
+        message.write_u32::<LittleEndian>(checksum).unwrap();
         message.write_u32::<LittleEndian>(self.magic).unwrap();
         message
     }
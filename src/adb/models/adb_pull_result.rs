@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct AdbPullResult {
+    resume_offset: u64,
+    bytes_pulled: u64,
+    sha256: String,
+}
+
+impl AdbPullResult {
+    pub fn new(resume_offset: u64, bytes_pulled: u64, sha256: String) -> Self {
+        AdbPullResult { resume_offset, bytes_pulled, sha256 }
+    }
+
+    pub fn message(&self) -> String {
+        format!("Pulled {} bytes from offset {}, sha256 {}", self.bytes_pulled, self.resume_offset, self.sha256)
+    }
+}
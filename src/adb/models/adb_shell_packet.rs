@@ -0,0 +1,31 @@
+use byteorder::{LittleEndian, WriteBytesExt};
+use crate::adb::enums::adb_shell_packet_id::AdbShellPacketId;
+
+#[derive(Debug, Clone)]
+pub struct AdbShellPacket {
+    id: AdbShellPacketId,
+    data: Vec<u8>,
+}
+
+impl AdbShellPacket {
+    const HEADER_SIZE: usize = 5;
+
+    pub fn new(id: AdbShellPacketId, data: Vec<u8>) -> Self {
+        AdbShellPacket {
+            id,
+            data,
+        }
+    }
+
+    pub fn exit(exit_code: u8) -> Self {
+        Self::new(AdbShellPacketId::Exit, vec![exit_code])
+    }
+
+    pub fn pack_packet(&self) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(Self::HEADER_SIZE + self.data.len());
+        packet.push(self.id as u8);
+        packet.write_u32::<LittleEndian>(self.data.len() as u32).unwrap();
+        packet.extend_from_slice(&self.data);
+        packet
+    }
+}
@@ -20,10 +20,6 @@ impl AdbTransactionInfo {
         self.receive_packet_id
     }
 
-    pub fn args_match(&self, arg0: u32, arg1: u32) -> bool {
-        arg1 == self.sent_packet_id && (self.receive_packet_id == 0 || arg0 == self.receive_packet_id)
-    }
-
     pub fn set_receive_packet_id(&mut self, receive_packet_id: u32) {
         self.receive_packet_id = receive_packet_id;
     }
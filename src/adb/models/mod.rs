@@ -5,4 +5,9 @@ pub(crate) mod adb_port_forward_info;
 pub(crate) mod adb_task;
 pub(crate) mod adb_transaction_info;
 pub(crate) mod adb_port_reverse;
-pub(crate) mod adb_port_reverse_info;
\ No newline at end of file
+pub(crate) mod adb_port_reverse_info;
+pub(crate) mod adb_device_banner;
+pub(crate) mod adb_shell_packet;
+pub(crate) mod adb_install_result;
+pub(crate) mod adb_pull_result;
+pub(crate) mod adb_key_fingerprint;
\ No newline at end of file
@@ -1,2 +0,0 @@
-mod adb_packet_store;
-pub use adb_packet_store::AdbPacketStore;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use tokio::net::TcpStream;
+use tracing::warn;
+use crate::adb::io::socket::send_fail_with_response;
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{ABB_EXEC_COMMAND, ABB_EXEC_FEATURE, ABB_FEATURE};
+
+impl AdbServer {
+    pub async fn handle_abb_command(adb_device: &AdbDevice, socket: &mut TcpStream, abb_command: String) {
+        let required_feature = if abb_command.starts_with(ABB_EXEC_COMMAND) { ABB_EXEC_FEATURE } else { ABB_FEATURE };
+        if !adb_device.has_feature(required_feature) {
+            if let Err(err) = send_fail_with_response(socket, Some(format!("device doesn't support {}", required_feature))).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
+            return;
+        }
+
+        let Some(transaction_info) = Self::open_device_service(adb_device, socket, abb_command).await else {
+            return;
+        };
+
+        if let Err(err) = adb_device.adb_device_connection().relay_stream(&transaction_info, socket, None).await {
+            warn!("{} session failed: {}", required_feature, err);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use tokio::net::TcpStream;
+use tracing::warn;
+use crate::adb::io::socket::send_ok_with_response;
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+
+impl AdbServer {
+    pub async fn handle_auth_info_command(adb_device: &AdbDevice, socket: &mut TcpStream) {
+        let mut auth_info = format!("state: {}\n", adb_device.adb_device_status());
+        if let Some(auth_key_fingerprint) = adb_device.auth_key_fingerprint() {
+            auth_info.push_str(&format!("key: {}\nmd5: {}\nsha256: {}\n", auth_key_fingerprint.public_key_path(), auth_key_fingerprint.md5(), auth_key_fingerprint.sha256()));
+        }
+
+        if let Err(err) = send_ok_with_response(socket, Some(auth_info)).await {
+            warn!("Failed to send auth info to client: {}", err);
+        }
+    }
+}
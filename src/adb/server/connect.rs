@@ -0,0 +1,219 @@
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time;
+use tracing::{info, warn};
+use crate::adb::enums::adb_device_status::AdbDeviceStatus;
+use crate::adb::io::socket::{send_fail_with_response, send_ok_with_response};
+use crate::adb::server::server::{ADB_SERVER_INSTANCE, AdbServer};
+use crate::constants::DEFAULT_ADB_TCP_PORT;
+use crate::transport::enums::interface_type::InterfaceType;
+use crate::transport::tcp_transport::TcpTransport;
+
+impl AdbServer {
+    const WAIT_FOR_PENDING_CONNECTION_MS: u64 = 100;
+    const PENDING_CONNECTION_TIMEOUT_SECONDS: u64 = 15;
+
+    pub async fn handle_connect_command(socket: &mut TcpStream, device_address: &str) {
+        let response = match Self::normalize_device_address(device_address) {
+            Some(device_address) => Self::connect_tcp_device(device_address).await,
+            None => "empty address".to_string(),
+        };
+
+        info!("{}", response);
+        if let Err(e) = send_ok_with_response(socket, Some(response)).await {
+            warn!("Failed to send connect response: {:?}", e);
+        }
+    }
+
+    pub async fn handle_disconnect_command(socket: &mut TcpStream, device_address: &str) {
+        let result = match Self::normalize_device_address(device_address) {
+            Some(device_address) => {
+                let was_registered = ADB_SERVER_INSTANCE.tcp_devices_registry.remove(&device_address).is_some();
+                let was_connected = Self::disconnect_tcp_device(&device_address).await;
+                if was_registered || was_connected {
+                    Ok(format!("disconnected {}", device_address))
+                } else {
+                    Err(format!("no such device '{}'", device_address))
+                }
+            }
+            None => {
+                let registered_addresses: Vec<String> = ADB_SERVER_INSTANCE.tcp_devices_registry.iter().map(|address| address.clone()).collect();
+                ADB_SERVER_INSTANCE.tcp_devices_registry.clear();
+                for device_address in registered_addresses {
+                    Self::disconnect_tcp_device(&device_address).await;
+                }
+                Ok("disconnected everything".to_string())
+            }
+        };
+
+        let send_result = match result {
+            Ok(response) => {
+                info!("{}", response);
+                send_ok_with_response(socket, Some(response)).await
+            }
+            Err(response) => send_fail_with_response(socket, Some(response)).await,
+        };
+        if let Err(e) = send_result {
+            warn!("Failed to send disconnect response: {:?}", e);
+        }
+    }
+
+    async fn connect_tcp_device(device_address: String) -> String {
+        if !Self::wait_for_pending_connection(&device_address).await {
+            return format!("failed to connect to {}: connection is still pending", device_address);
+        }
+
+        if ADB_SERVER_INSTANCE.adb_devices_hashmap.contains_key(&device_address) {
+            return format!("already connected to {}", device_address);
+        }
+
+        let transport = TcpTransport::new(device_address.clone());
+        Self::on_find_device(device_address.clone(), Box::new(transport), InterfaceType::android_tcp()).await;
+
+        let device_status = ADB_SERVER_INSTANCE.adb_devices_hashmap.get(&device_address)
+            .and_then(|entry| entry.value().as_ref().map(|adb_device| adb_device.adb_device_status().clone()));
+
+        match device_status {
+            Some(AdbDeviceStatus::Offline(err)) => {
+                Self::disconnect_tcp_device(&device_address).await;
+                format!("failed to connect to {}: {}", device_address, err)
+            }
+            Some(AdbDeviceStatus::Unauthorized) => {
+                ADB_SERVER_INSTANCE.tcp_devices_registry.insert(device_address.clone());
+                format!("failed to authenticate to {}", device_address)
+            }
+            Some(AdbDeviceStatus::Available) => {
+                ADB_SERVER_INSTANCE.tcp_devices_registry.insert(device_address.clone());
+                format!("connected to {}", device_address)
+            }
+            None => format!("failed to connect to {}", device_address),
+        }
+    }
+
+    async fn disconnect_tcp_device(device_address: &str) -> bool {
+        let removed_device = ADB_SERVER_INSTANCE.adb_devices_hashmap.remove_if(device_address, |_, adb_device| {
+            adb_device.as_ref().is_some_and(|adb_device| adb_device.is_tcp_device())
+        });
+
+        match removed_device {
+            Some((_, Some(adb_device))) => {
+                adb_device.close_device_gracefully().await;
+                adb_device.adb_device_connection().close().await;
+                Self::notify_devices_changed();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    async fn wait_for_pending_connection(device_address: &str) -> bool {
+        let start_time = std::time::Instant::now();
+        while Self::is_connection_pending(device_address) {
+            if start_time.elapsed().as_secs() >= Self::PENDING_CONNECTION_TIMEOUT_SECONDS {
+                return false;
+            }
+            time::sleep(Duration::from_millis(Self::WAIT_FOR_PENDING_CONNECTION_MS)).await;
+        }
+        true
+    }
+
+    fn is_connection_pending(device_address: &str) -> bool {
+        ADB_SERVER_INSTANCE.adb_devices_hashmap.get(device_address)
+            .is_some_and(|entry| entry.value().is_none())
+    }
+
+    fn normalize_device_address(device_address: &str) -> Option<String> {
+        let device_address = device_address.trim();
+        if device_address.is_empty() {
+            return None;
+        }
+
+        let has_port = device_address.rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+
+        if has_port {
+            Some(device_address.to_string())
+        } else {
+            Some(format!("{}:{}", device_address, DEFAULT_ADB_TCP_PORT))
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use crate::adb::models::adb_message::AdbMessage;
+    use crate::adb::server::server::{ADB_SERVER_INSTANCE, AdbServer};
+    use crate::constants::{ADB_MESSAGE_SIZE, ADB_PROTOCOL_VERSION, AUTH_CODE, CNXN_CODE, MAX_ADB_DATA};
+
+    const AUTH_TOKEN: u32 = 1;
+    const AUTH_SIGNATURE: u32 = 2;
+
+    pub(crate) async fn spawn_fake_adbd(is_authorized: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let device_address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve_fake_adbd(socket, is_authorized));
+            }
+        });
+        device_address
+    }
+
+    async fn serve_fake_adbd(mut socket: TcpStream, is_authorized: bool) {
+        let mut header = [0u8; ADB_MESSAGE_SIZE];
+        while socket.read_exact(&mut header).await.is_ok() {
+            let (command, arg0, _, data_length, _) = AdbMessage::unpack_message(&header.to_vec()).unwrap();
+            let mut data = vec![0u8; data_length as usize];
+            if socket.read_exact(&mut data).await.is_err() {
+                break;
+            }
+
+            let response = match command {
+                CNXN_CODE if is_authorized => AdbMessage::new(CNXN_CODE, ADB_PROTOCOL_VERSION, MAX_ADB_DATA, b"device::ro.product.name=fake;features=shell_v2".to_vec()),
+                CNXN_CODE => AdbMessage::new(AUTH_CODE, AUTH_TOKEN, 0, vec![0x5a; 20]),
+                AUTH_CODE if arg0 == AUTH_SIGNATURE => AdbMessage::new(AUTH_CODE, AUTH_TOKEN, 0, vec![0xa5; 20]),
+                _ => continue,
+            };
+            let mut packet = response.pack_message(true);
+            packet.extend_from_slice(response.data());
+            if socket.write_all(&packet).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_reports_connected_for_authorized_device() {
+        let device_address = spawn_fake_adbd(true).await;
+
+        let response = AdbServer::connect_tcp_device(device_address.clone()).await;
+
+        assert_eq!(response, format!("connected to {}", device_address));
+        assert!(ADB_SERVER_INSTANCE.tcp_devices_registry.contains(&device_address));
+        assert_eq!(AdbServer::connect_tcp_device(device_address.clone()).await, format!("already connected to {}", device_address));
+    }
+
+    #[tokio::test]
+    async fn connect_reports_failed_authentication_for_unauthorized_device() {
+        let device_address = spawn_fake_adbd(false).await;
+
+        let response = AdbServer::connect_tcp_device(device_address.clone()).await;
+
+        assert_eq!(response, format!("failed to authenticate to {}", device_address));
+    }
+
+    #[tokio::test]
+    async fn connect_reports_failure_when_nothing_listens() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let device_address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let response = AdbServer::connect_tcp_device(device_address.clone()).await;
+
+        assert!(response.starts_with(&format!("failed to connect to {}", device_address)), "{}", response);
+        assert!(!ADB_SERVER_INSTANCE.adb_devices_hashmap.contains_key(&device_address));
+    }
+}
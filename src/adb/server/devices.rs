@@ -1,30 +1,133 @@
-use tracing::{error};
+use tracing::{error, info, warn};
+use tokio::io::AsyncReadExt;
 use tokio::net::TcpStream;
-use crate::adb::io::socket::send_ok_with_response;
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::io::socket::{send_full_response, send_ok_with_response};
 use crate::adb::server::server::{ADB_SERVER_INSTANCE, AdbServer};
+use crate::constants::OKAY;
 
 impl AdbServer{
+    const TRACK_DEVICES_CLIENT_BUFFER_SIZE: usize = 64;
+
     pub async fn adb_devices(client_socket: &mut TcpStream) {
         let response = Self::get_active_devices();
         if let Err(err) = send_ok_with_response(client_socket, Some(response)).await {
             error!("Failed get adb devices {}", err);
         }
     }
-     fn get_active_devices() -> String {
-        let mut devices_result = String::new();
-        if !ADB_SERVER_INSTANCE.adb_devices_hashmap.is_empty() {
-            for entry in ADB_SERVER_INSTANCE.adb_devices_hashmap.iter() {
-                let (serial_number, adb_device) = entry.pair();
-                match adb_device {
-                    None => {}
-                    Some(adb_device) => {
-                        devices_result.push_str(&format!("{} {}\n", serial_number, adb_device.adb_device_status()));
+
+    pub async fn adb_devices_long(client_socket: &mut TcpStream) {
+        let devices_list = Self::get_devices_long_list();
+        let response = format!("{}{:04x}{}", OKAY, devices_list.len(), devices_list);
+        if let Err(err) = send_full_response(client_socket, response).await {
+            error!("Failed get adb devices long list {}", err);
+        }
+    }
+
+    pub async fn track_devices(client_socket: &mut TcpStream) {
+        if let Err(err) = send_ok_with_response(client_socket, None).await {
+            warn!("Failed to send OK response for track devices: {}", err);
+            return;
+        }
+
+        let mut devices_changed_receiver = ADB_SERVER_INSTANCE.devices_changed_sender.subscribe();
+        let mut last_devices_list: Option<String> = None;
+        let mut client_buffer = [0u8; Self::TRACK_DEVICES_CLIENT_BUFFER_SIZE];
+
+        loop {
+            let devices_list = Self::get_devices_list();
+            if last_devices_list.as_ref() != Some(&devices_list) {
+                let response = format!("{:04x}{}", devices_list.len(), devices_list);
+                if let Err(err) = send_full_response(client_socket, response).await {
+                    info!("Track devices client disconnected: {}", err);
+                    return;
+                }
+                last_devices_list = Some(devices_list);
+            }
+
+            tokio::select! {
+                changed = devices_changed_receiver.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                }
+                read_result = client_socket.read(&mut client_buffer) => {
+                    match read_result {
+                        Ok(0) | Err(_) => {
+                            info!("Track devices client closed the connection");
+                            return;
+                        }
+                        Ok(_) => {}
                     }
                 }
             }
-            devices_result
+        }
+    }
+
+    pub fn notify_devices_changed() {
+        ADB_SERVER_INSTANCE.devices_changed_sender.send_modify(|_| {});
+    }
+
+    fn get_active_devices() -> String {
+        if !ADB_SERVER_INSTANCE.adb_devices_hashmap.is_empty() {
+            Self::get_devices_list()
         } else {
             String::from("No devices found")
         }
     }
-}
\ No newline at end of file
+
+    fn get_devices_list() -> String {
+        let mut devices_result = String::new();
+        for entry in ADB_SERVER_INSTANCE.adb_devices_hashmap.iter() {
+            let (serial_number, adb_device) = entry.pair();
+            match adb_device {
+                None => {}
+                Some(adb_device) => {
+                    devices_result.push_str(&format!("{} {}\n", serial_number, adb_device.adb_device_status()));
+                }
+            }
+        }
+        devices_result
+    }
+
+    fn get_devices_long_list() -> String {
+        let mut devices_result = String::new();
+        for entry in ADB_SERVER_INSTANCE.adb_devices_hashmap.iter() {
+            let (serial_number, adb_device) = entry.pair();
+            if let Some(adb_device) = adb_device {
+                devices_result.push_str(&Self::format_device_long(serial_number, adb_device));
+            }
+        }
+        devices_result
+    }
+
+    fn format_device_long(serial_number: &str, adb_device: &AdbDevice) -> String {
+        let mut device_line = format!("{:<22} {}", serial_number, adb_device.adb_device_status());
+
+        if let Some(device_path) = adb_device.device_path() {
+            device_line.push_str(&format!(" {}", device_path));
+        }
+
+        if let Some(device_banner) = adb_device.device_banner() {
+            let banner_fields = [
+                ("product", device_banner.product()),
+                ("model", device_banner.model()),
+                ("device", device_banner.device()),
+            ];
+            for (field_name, field_value) in banner_fields {
+                if let Some(field_value) = field_value {
+                    device_line.push_str(&format!(" {}:{}", field_name, Self::sanitize_device_field(field_value)));
+                }
+            }
+        }
+
+        device_line.push_str(&format!(" transport_id:{}\n", adb_device.transport_id()));
+        device_line
+    }
+
+    fn sanitize_device_field(value: &str) -> String {
+        value.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_' { c } else { '_' })
+            .collect()
+    }
+}
@@ -0,0 +1,32 @@
+use tokio::net::TcpStream;
+use tracing::warn;
+use crate::adb::io::socket::{send_fail_with_response, send_full_response, send_ok_with_response};
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{ADB_SERVER_FEATURES, OKAY};
+
+const FEATURES_SEPARATOR: &str = ",";
+
+impl AdbServer {
+    pub async fn handle_features_command(adb_device: &AdbDevice, socket: &mut TcpStream) {
+        let features = adb_device.supported_features().join(FEATURES_SEPARATOR);
+        let response = format!("{}{:04x}{}", OKAY, features.len(), features);
+        if let Err(e) = send_full_response(socket, response).await {
+            warn!("Failed to send OK response with device features: {:?}", e);
+        }
+    }
+
+    pub async fn handle_host_features_command(socket: &mut TcpStream) {
+        let features = ADB_SERVER_FEATURES.join(FEATURES_SEPARATOR);
+        if let Err(e) = send_ok_with_response(socket, Some(features)).await {
+            warn!("Failed to send OK response with host features: {:?}", e);
+        }
+    }
+
+    pub async fn handle_invalid_host_serial_command(socket: &mut TcpStream, command: &str) {
+        warn!("Invalid host-serial request: {}", command);
+        if let Err(e) = send_fail_with_response(socket, Some(format!("invalid host-serial request: {}", command))).await {
+            warn!("Failed to send FAIL response: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use tokio::net::TcpStream;
+use tracing::{error, info, warn};
+use crate::adb::enums::adb_install_method::AdbInstallMethod;
+use crate::adb::io::socket::{send_fail_with_response, send_ok_with_response};
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{ABB_EXEC_FEATURE, ADBR_INSTALL_ARGS_SEPARATOR, ADBR_INSTALL_COMMAND, ADBR_INSTALL_WITH_ARGS_COMMAND, CMD_FEATURE, NULL_TERMINATOR};
+
+impl AdbServer {
+    const INSTALL_OPERATION_TIMEOUT_SECONDS: f64 = 120.0;
+
+    pub async fn handle_adbr_install_command(adb_device: &AdbDevice, socket: &mut TcpStream, install_command: String) {
+        let (install_args, apk_paths) = Self::parse_install_command(&install_command);
+        if apk_paths.is_empty() {
+            if let Err(err) = send_fail_with_response(socket, Some("No APK files given".to_string())).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
+            return;
+        }
+
+        let install_method = if adb_device.has_feature(ABB_EXEC_FEATURE) {
+            AdbInstallMethod::AbbExec
+        } else if adb_device.has_feature(CMD_FEATURE) {
+            AdbInstallMethod::Cmd
+        } else {
+            AdbInstallMethod::Legacy
+        };
+
+        let install_result = adb_device.adb_device_connection()
+            .adb_install(&apk_paths, &install_args, install_method, Some(Self::INSTALL_OPERATION_TIMEOUT_SECONDS))
+            .await;
+
+        let send_result = match install_result {
+            Ok(install_result) if install_result.is_success() => {
+                info!("Install succeeded on {}", adb_device.device_serial_number());
+                send_ok_with_response(socket, Some(install_result.message().to_string())).await
+            }
+            Ok(install_result) => {
+                warn!("Install failed on {}: {}", adb_device.device_serial_number(), install_result.message());
+                send_fail_with_response(socket, Some(install_result.message().to_string())).await
+            }
+            Err(err) => {
+                error!("Install error on {}: {}", adb_device.device_serial_number(), err);
+                send_fail_with_response(socket, Some(err.to_string())).await
+            }
+        };
+
+        if let Err(err) = send_result {
+            warn!("Failed to send install result to client: {}", err);
+        }
+    }
+
+    fn parse_install_command(install_command: &str) -> (Vec<String>, Vec<String>) {
+        let (install_args, apk_paths) = match install_command.strip_prefix(ADBR_INSTALL_WITH_ARGS_COMMAND).and_then(|command| command.split_once(':')) {
+            Some((install_args, apk_paths)) => (install_args, apk_paths),
+            None => ("", install_command.strip_prefix(ADBR_INSTALL_COMMAND).unwrap_or_default()),
+        };
+
+        let install_args = install_args.split(ADBR_INSTALL_ARGS_SEPARATOR)
+            .filter(|install_arg| !install_arg.is_empty())
+            .map(str::to_string)
+            .collect();
+        let apk_paths = apk_paths.split(NULL_TERMINATOR)
+            .filter(|apk_path| !apk_path.is_empty())
+            .map(str::to_string)
+            .collect();
+        (install_args, apk_paths)
+    }
+}
@@ -0,0 +1,33 @@
+use tokio::net::TcpStream;
+use tracing::warn;
+use crate::adb::io::socket::send_fail_with_response;
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{TRACK_APP_COMMAND, TRACK_APP_FEATURE, TRACK_JDWP_COMMAND};
+
+impl AdbServer {
+    pub async fn handle_track_jdwp_command(adb_device: &AdbDevice, socket: &mut TcpStream) {
+        Self::relay_tracking_service(adb_device, socket, TRACK_JDWP_COMMAND).await;
+    }
+
+    pub async fn handle_track_app_command(adb_device: &AdbDevice, socket: &mut TcpStream) {
+        if !adb_device.has_feature(TRACK_APP_FEATURE) {
+            if let Err(err) = send_fail_with_response(socket, Some("device doesn't support track-app".to_string())).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
+            return;
+        }
+
+        Self::relay_tracking_service(adb_device, socket, TRACK_APP_COMMAND).await;
+    }
+
+    async fn relay_tracking_service(adb_device: &AdbDevice, socket: &mut TcpStream, service: &str) {
+        let Some(transaction_info) = Self::open_device_service(adb_device, socket, service.to_string()).await else {
+            return;
+        };
+
+        if let Err(err) = adb_device.adb_device_connection().relay_stream(&transaction_info, socket, None).await {
+            warn!("{} session failed: {}", service, err);
+        }
+    }
+}
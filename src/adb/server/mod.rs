@@ -4,7 +4,15 @@ pub mod port_forward;
 pub mod transport;
 pub mod scripting;
 pub mod shell;
+pub mod jdwp;
+pub mod abb;
+pub mod install;
+pub mod pull;
+pub mod sync_tree;
 pub mod sync;
+pub mod features;
+pub mod connect;
+pub mod auth_info;
 mod security;
 mod port_reverse;
 mod actions;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use tokio::net::TcpStream;
+use tracing::{error, info, warn};
+use crate::adb::io::socket::{send_fail_with_response, send_ok_with_response};
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{ADBR_PULL_COMMAND, NULL_TERMINATOR};
+
+impl AdbServer {
+    const PULL_OPERATION_TIMEOUT_SECONDS: f64 = 120.0;
+
+    pub async fn handle_adbr_pull_command(adb_device: &AdbDevice, socket: &mut TcpStream, pull_command: String) {
+        let pull_paths = pull_command.strip_prefix(ADBR_PULL_COMMAND)
+            .and_then(|pull_paths| pull_paths.trim_end_matches(NULL_TERMINATOR).split_once(NULL_TERMINATOR))
+            .filter(|(remote_path, local_path)| !remote_path.is_empty() && !local_path.is_empty());
+        let Some((remote_path, local_path)) = pull_paths else {
+            if let Err(err) = send_fail_with_response(socket, Some(format!("Expected {}<remote path>\\0<local path>", ADBR_PULL_COMMAND))).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
+            return;
+        };
+
+        let pull_result = adb_device.adb_device_connection()
+            .adb_pull(remote_path, local_path, Some(Self::PULL_OPERATION_TIMEOUT_SECONDS))
+            .await;
+
+        let send_result = match pull_result {
+            Ok(pull_result) => {
+                info!("Pull of {} from {} succeeded: {}", remote_path, adb_device.device_serial_number(), pull_result.message());
+                send_ok_with_response(socket, Some(pull_result.message())).await
+            }
+            Err(err) => {
+                error!("Pull of {} from {} failed: {}", remote_path, adb_device.device_serial_number(), err);
+                send_fail_with_response(socket, Some(err.to_string())).await
+            }
+        };
+
+        if let Err(err) = send_result {
+            warn!("Failed to send pull result to client: {}", err);
+        }
+    }
+}
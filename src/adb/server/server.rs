@@ -1,10 +1,12 @@
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread::{spawn};
 use std::time::Duration;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use lazy_static::lazy_static;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc::{self, Sender, Receiver};
+use tokio::sync::watch;
 use tokio::net::{TcpListener, TcpStream};
 use crate::adb::connections::adb_connection::AdbConnection;
 use futures::stream::{self, StreamExt};
@@ -24,9 +26,10 @@ use crate::adb::models::adb_device::AdbDevice;
 use crate::adb::models::adb_task::AdbTask;
 use crate::adb::server::actions::executor::execute_action;
 use crate::adb::server::actions::models::action_config::ActionConfig;
-use crate::constants::{EXIT_FAILURE, HOST_DEVICES_COMMAND, HOST_EMULATOR_ANY_COMMAND, HOST_TRANSPORT_ANY_COMMAND, HOST_TRANSPORT_COMMAND, HOST_USB_ANY_COMMAND, HOST_VERSION_COMMAND, SHELL_COMMAND, HOST_FORWARD_COMMAND, HOST_KILL_FORWARD_COMMAND, HOST_FORWARD_KILL_ALL_COMMAND, REBOOT_COMMAND, SYNC_COMMAND, SYNC_STAT_COMMAND_STR, SYNC_SEND_COMMAND_STR, SYNC_DATA_COMMAND_STR, SYNC_QUIT_COMMAND_STR, SYNC_RECV_COMMAND_STR, SYNC_DENT_COMMAND_STR, HOST_SERIALNO_COMMAND, HOST_GET_DEVPATH_COMMAND, ROOT_COMMAND, UNROOT_COMMAND, REMOUNT_COMMAND, ENABLE_VERITY_COMMAND, DISABLE_VERITY_COMMAND, HOST_FORWARD_LIST_COMMAND, HOST_GET_STATE_COMMAND, REVERSE_FORWARD_COMMAND, REVERSE_KILL_FORWARD_COMMAND, REVERSE_KILL_ALL_FORWARD_COMMAND, REVERSE_FORWARD_LIST_COMMAND, OKAY, ADB_SERVER_VERSION, DEFAULT_ADB_SERVER_PORT, CONNECT_EVENT, DISCONNECT_EVENT};
+use crate::constants::{EXIT_FAILURE, HOST_DEVICES_COMMAND, HOST_EMULATOR_ANY_COMMAND, HOST_TRANSPORT_ANY_COMMAND, HOST_TRANSPORT_COMMAND, HOST_USB_ANY_COMMAND, HOST_VERSION_COMMAND, SHELL_COMMAND, SHELL_WITH_ARGS_COMMAND, EXEC_COMMAND, TRACK_JDWP_COMMAND, TRACK_APP_COMMAND, ABB_COMMAND, ABB_EXEC_COMMAND, ADBR_INSTALL_COMMAND, ADBR_INSTALL_WITH_ARGS_COMMAND, ADBR_PULL_COMMAND, ADBR_SYNC_COMMAND, HOST_FORWARD_COMMAND, HOST_KILL_FORWARD_COMMAND, HOST_FORWARD_KILL_ALL_COMMAND, REBOOT_COMMAND, SYNC_COMMAND, SYNC_STAT_COMMAND_STR, SYNC_SEND_COMMAND_STR, SYNC_DATA_COMMAND_STR, SYNC_QUIT_COMMAND_STR, SYNC_RECV_COMMAND_STR, SYNC_DENT_COMMAND_STR, HOST_SERIALNO_COMMAND, HOST_GET_DEVPATH_COMMAND, ROOT_COMMAND, UNROOT_COMMAND, REMOUNT_COMMAND, ENABLE_VERITY_COMMAND, DISABLE_VERITY_COMMAND, HOST_FORWARD_LIST_COMMAND, HOST_GET_STATE_COMMAND, HOST_AUTH_INFO_COMMAND, REVERSE_FORWARD_COMMAND, REVERSE_KILL_FORWARD_COMMAND, REVERSE_KILL_ALL_FORWARD_COMMAND, REVERSE_FORWARD_LIST_COMMAND, OKAY, ADB_SERVER_VERSION, DEFAULT_ADB_SERVER_PORT, CONNECT_EVENT, DISCONNECT_EVENT, HOST_TRACK_DEVICES_COMMAND, HOST_DEVICES_LONG_COMMAND, HOST_FEATURES_COMMAND, HOST_HOST_FEATURES_COMMAND, HOST_SERIAL_COMMAND, HOST_CONNECT_COMMAND, HOST_DISCONNECT_COMMAND, EMULATOR_SERIAL_PREFIX};
 use crate::transport::enums::interface_type::InterfaceType;
 use crate::transport::transport::Transport;
+use crate::utils::utils::{ensure_adb_key_pair, get_adb_key_path};
 
 
 
@@ -38,6 +41,9 @@ pub struct AdbServer {
     pub adb_devices_hashmap: DashMap<String, Option<Arc<AdbDevice>>>,
     task_sender: Sender<AdbTask>,
     task_receiver: Arc<Mutex<Receiver<AdbTask>>>,
+    pub devices_changed_sender: watch::Sender<()>,
+    pub tcp_devices_registry: Arc<DashSet<String>>,
+    last_transport_id: AtomicU64,
 }
 
 impl AdbServer {
@@ -50,10 +56,14 @@ impl AdbServer {
 
     fn new() -> AdbServer {
         let (task_sender, task_receiver) = mpsc::channel(Self::TASK_CHANNEL_SIZE);
+        let (devices_changed_sender, _) = watch::channel(());
         AdbServer {
             adb_devices_hashmap: DashMap::new(),
             task_sender,
             task_receiver: Arc::new(Mutex::new(task_receiver)),
+            devices_changed_sender,
+            tcp_devices_registry: Arc::new(DashSet::new()),
+            last_transport_id: AtomicU64::new(0),
         }
     }
 
@@ -62,13 +72,17 @@ impl AdbServer {
     }
 
     pub async fn init(&self, server_listen_address: String, server_port: Option<u16>) {
+        Self::ensure_adb_keys();
+
+        let tcp_devices_registry = self.tcp_devices_registry.clone();
         let scanning_for_devices_task = spawn(move || {
             let rt = Runtime::new().unwrap();
             rt.block_on(async {
-                DevicesScanner::new().start_scanning(
+                DevicesScanner::new(tcp_devices_registry).start_scanning(
                     InterfaceType::android_usb(),
                     Arc::new(|serial: String| Self::is_new_device(serial)),
                     Arc::new(|serial_number: String, transport: Box<dyn Transport>, interface_type: InterfaceType| Box::pin(Self::on_find_device(serial_number, transport, interface_type))),
+                    Arc::new(|serial_number: String| Box::pin(Self::on_lost_device(serial_number))),
                 ).await;
             });
         });
@@ -117,6 +131,22 @@ impl AdbServer {
         });
     }
 
+    fn ensure_adb_keys() {
+        let adb_keys_path = match get_adb_key_path() {
+            Ok(adb_keys_path) => adb_keys_path,
+            Err(err) => {
+                error!("Failed to resolve ADB key path: {}", err);
+                return;
+            }
+        };
+
+        match ensure_adb_key_pair(&adb_keys_path) {
+            Ok(true) => info!("Generated a new ADB RSA key pair in {}", adb_keys_path),
+            Ok(false) => {}
+            Err(err) => error!("Failed to prepare ADB RSA keys in {}: {}", adb_keys_path, err),
+        }
+    }
+
     async fn start_listen_client_requests(server_listen_address: String, server_port: Option<u16>) {
         let port = server_port.unwrap_or(DEFAULT_ADB_SERVER_PORT);
         let server_address = format!("{}:{}", server_listen_address, port);
@@ -208,12 +238,60 @@ impl AdbServer {
                 }
                 command if command.starts_with(HOST_TRANSPORT_COMMAND) => {
                     let serial_number = &command[HOST_TRANSPORT_COMMAND.len()..];
-                    chosen_adb_device = Self::execute_transport_command(socket, AdbDeviceTransport::usb(serial_number)).await;
+                    chosen_adb_device = Self::execute_transport_command(socket, AdbDeviceTransport::serial(serial_number)).await;
                     if chosen_adb_device.is_none() {
                         info!("No device found for serial: {}", serial_number);
                         break;
                     }
                 }
+                command if command.starts_with(HOST_SERIAL_COMMAND) => {
+                    let Some((serial_number, service)) = Self::parse_host_serial_command(command) else {
+                        Self::handle_invalid_host_serial_command(socket, command).await;
+                        break;
+                    };
+                    match Self::get_adb_device_by_serial(&serial_number) {
+                        Ok(adb_device) => {
+                            chosen_adb_device = Some(adb_device);
+                            request = service;
+                            continue;
+                        }
+                        Err(err) => {
+                            if let Err(err) = send_fail_with_response(socket, Some(err.to_string())).await {
+                                warn!("Failed to send failure response to client: {}", err);
+                            }
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(HOST_HOST_FEATURES_COMMAND) => {
+                    Self::handle_host_features_command(socket).await;
+                    break;
+                }
+                command if command.starts_with(HOST_FEATURES_COMMAND) => {
+                    let adb_device = match chosen_adb_device {
+                        Some(ref adb_device) => Ok(adb_device.clone()),
+                        None => Self::get_adb_device_by_device_transport(AdbDeviceTransport::Any),
+                    };
+                    match adb_device {
+                        Ok(adb_device) => Self::handle_features_command(&adb_device, socket).await,
+                        Err(err) => {
+                            if let Err(err) = send_fail_with_response(socket, Some(err.to_string())).await {
+                                warn!("Failed to send failure response to client: {}", err);
+                            }
+                        }
+                    }
+                    break;
+                }
+                command if command.starts_with(HOST_CONNECT_COMMAND) => {
+                    let device_address = command[HOST_CONNECT_COMMAND.len()..].to_string();
+                    Self::handle_connect_command(socket, &device_address).await;
+                    break;
+                }
+                command if command.starts_with(HOST_DISCONNECT_COMMAND) => {
+                    let device_address = command[HOST_DISCONNECT_COMMAND.len()..].to_string();
+                    Self::handle_disconnect_command(socket, &device_address).await;
+                    break;
+                }
                 command if command.starts_with(HOST_VERSION_COMMAND) => {
                     Self::send_version_response(socket).await;
                     break;
@@ -232,10 +310,25 @@ impl AdbServer {
                         }
                     }
                 }
+                command if command.starts_with(HOST_AUTH_INFO_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => Self::handle_auth_info_command(adb_device, socket).await,
+                        None => Self::handle_no_device_selected(socket).await,
+                    }
+                    break;
+                }
+                command if command.starts_with(HOST_DEVICES_LONG_COMMAND) => {
+                    Self::adb_devices_long(socket).await;
+                    break;
+                }
                 command if command.starts_with(HOST_DEVICES_COMMAND) => {
                     Self::adb_devices(socket).await;
                     break;
                 }
+                command if command.starts_with(HOST_TRACK_DEVICES_COMMAND) => {
+                    Self::track_devices(socket).await;
+                    break;
+                }
                 command if command.starts_with(REBOOT_COMMAND) => {
                     match chosen_adb_device {
                         Some(ref adb_device) => {
@@ -332,7 +425,7 @@ impl AdbServer {
                         }
                     }
                 }
-                command if command.starts_with(SHELL_COMMAND) => {
+                command if command.starts_with(SHELL_COMMAND) || command.starts_with(SHELL_WITH_ARGS_COMMAND) => {
                     match chosen_adb_device {
                         Some(ref adb_device) => {
                             Self::handle_shell_command(adb_device, socket, command.clone()).await;
@@ -344,6 +437,90 @@ impl AdbServer {
                         }
                     }
                 }
+                command if command.starts_with(EXEC_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_exec_command(adb_device, socket, command.clone()).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(ADBR_INSTALL_COMMAND) || command.starts_with(ADBR_INSTALL_WITH_ARGS_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_adbr_install_command(adb_device, socket, command.clone()).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(ADBR_SYNC_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_adbr_sync_command(adb_device, socket, command.clone()).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(ADBR_PULL_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_adbr_pull_command(adb_device, socket, command.clone()).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(ABB_COMMAND) || command.starts_with(ABB_EXEC_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_abb_command(adb_device, socket, command.clone()).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(TRACK_JDWP_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_track_jdwp_command(adb_device, socket).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
+                command if command.starts_with(TRACK_APP_COMMAND) => {
+                    match chosen_adb_device {
+                        Some(ref adb_device) => {
+                            Self::handle_track_app_command(adb_device, socket).await;
+                            break;
+                        }
+                        None => {
+                            Self::handle_no_device_selected(socket).await;
+                            break;
+                        }
+                    }
+                }
                 command if command.starts_with(HOST_FORWARD_COMMAND) => {
                     if let Err(err) = Self::handle_port_forward_command_set(socket, command.clone(), chosen_adb_device).await {
                         error!("Error handling port forward command: {}", err);
@@ -430,55 +607,160 @@ impl AdbServer {
         Self::close_client_connection(socket).await;
     }
 
-    async fn on_find_device(serial_number: String, transport: Box<dyn Transport>, interface_type: InterfaceType) {
+    pub async fn on_find_device(serial_number: String, transport: Box<dyn Transport>, interface_type: InterfaceType) {
         ADB_SERVER_INSTANCE.adb_devices_hashmap.insert(serial_number.clone(), None);
         let device_type = match interface_type {
             InterfaceType::AndroidUsb(..) => AdbDeviceType::Usb,
-            InterfaceType::AndroidTcp(..) => AdbDeviceType::Emulator,
+            InterfaceType::AndroidTcp(..) if serial_number.starts_with(EMULATOR_SERIAL_PREFIX) => AdbDeviceType::Emulator,
+            InterfaceType::AndroidTcp(..) => AdbDeviceType::Tcp,
         };
-        let mut adb_device_connection = match AdbDeviceConnection::new(None, transport) {
+        let device_path = transport.device_path();
+        let mut adb_device_connection = match AdbDeviceConnection::new(None, transport, interface_type) {
             Ok(adb_device_connection) => adb_device_connection,
             Err(_) => {
+                if device_type.is_network() {
+                    ADB_SERVER_INSTANCE.adb_devices_hashmap.remove(&serial_number);
+                }
                 return;
             }
         };
 
         match adb_device_connection.connect(None).await {
             Ok(_) => {
-                let adb_device = AdbDevice::new(serial_number.clone(), Arc::new(adb_device_connection), AdbDeviceStatus::Available, device_type, Duration::from_secs(Self::DEVICE_AVAILABLE_VERIFY_TIME_SECONDS));
+                let adb_device = Self::create_adb_device(serial_number.clone(), adb_device_connection, AdbDeviceStatus::Available, device_type, device_path, Duration::from_secs(Self::DEVICE_AVAILABLE_VERIFY_TIME_SECONDS));
                 ADB_SERVER_INSTANCE.adb_devices_hashmap.insert(serial_number.clone(), Some(Arc::new(adb_device)));
+                Self::notify_devices_changed();
                 info!("Device {} connected successfully", serial_number);
-                let serial_number_clone = serial_number.clone();
-                tokio::spawn(async move {
-                    match ActionConfig::load() {
-                        Ok(action_config) => {
-                            match execute_action(&action_config, &serial_number_clone, CONNECT_EVENT) {
-                                Ok(()) => info!("Successfully executed connect actions for device {}", serial_number_clone),
-                                Err(e) => error!("Failed to execute connect actions: {}", e),
-                            }
-                        }
-
-                        Err(e) => error!("Failed to load actions configuration: {}", e),
-                    }
-                });
+                Self::execute_connect_actions(serial_number);
             }
             Err(AdbConnectionError::Unauthorized(err)) => {
                 info!("Device {} is unauthorized: {}.", serial_number, err);
-                let adb_device = AdbDevice::new(serial_number.clone(), Arc::new(adb_device_connection), AdbDeviceStatus::Unauthorized, device_type, Duration::from_secs(Self::DEVICE_UNAUTHORIZED_RECONNECT_TIME_SECONDS));
-                ADB_SERVER_INSTANCE.adb_devices_hashmap.insert(serial_number.clone(), Some(Arc::new(adb_device)));
+                let adb_device = Arc::new(Self::create_adb_device(serial_number.clone(), adb_device_connection, AdbDeviceStatus::Unauthorized, device_type, device_path, Duration::from_secs(Self::DEVICE_UNAUTHORIZED_RECONNECT_TIME_SECONDS)));
+                if let Some(auth_key_fingerprint) = adb_device.auth_key_fingerprint() {
+                    info!("Device {} is waiting for key {} (MD5 {}) to be accepted", serial_number, auth_key_fingerprint.public_key_path(), auth_key_fingerprint.md5());
+                }
+                ADB_SERVER_INSTANCE.adb_devices_hashmap.insert(serial_number.clone(), Some(adb_device.clone()));
+                Self::notify_devices_changed();
+                Self::await_device_authorization(serial_number, adb_device);
             }
             Err(AdbConnectionError::DeviceNotAvailable(err)) => {
                 info!("Device {} is not available: {}.", serial_number, err);
-                let adb_device = AdbDevice::new(serial_number.clone(), Arc::new(adb_device_connection), AdbDeviceStatus::Offline(err), device_type, Duration::from_secs(Self::DEVICE_NOT_AVAILABLE_RECONNECT_TIME_SECONDS));
+                let adb_device = Self::create_adb_device(serial_number.clone(), adb_device_connection, AdbDeviceStatus::Offline(err), device_type, device_path, Duration::from_secs(Self::DEVICE_NOT_AVAILABLE_RECONNECT_TIME_SECONDS));
                 ADB_SERVER_INSTANCE.adb_devices_hashmap.insert(serial_number.clone(), Some(Arc::new(adb_device)));
+                Self::notify_devices_changed();
             }
             Err(err) => {
                 error!("Failed to connect to device {}: {}.", serial_number, err);
                 adb_device_connection.close().await;
+                if device_type.is_network() {
+                    ADB_SERVER_INSTANCE.adb_devices_hashmap.remove(&serial_number);
+                }
+            }
+        }
+    }
+
+    pub async fn on_lost_device(serial_number: String) {
+        let removed_device = ADB_SERVER_INSTANCE.adb_devices_hashmap.remove_if(&serial_number, |_, adb_device| {
+            adb_device.as_ref().is_none_or(|adb_device| adb_device.is_usb_device())
+        });
+
+        if let Some((_, adb_device)) = removed_device {
+            info!("Device {} disconnected", serial_number);
+            if let Some(adb_device) = adb_device {
+                adb_device.close_device_gracefully().await;
+                adb_device.adb_device_connection().close().await;
+                Self::execute_disconnect_actions(serial_number);
             }
+            Self::notify_devices_changed();
         }
     }
 
+    fn await_device_authorization(serial_number: String, unauthorized_device: Arc<AdbDevice>) {
+        tokio::spawn(async move {
+            loop {
+                if !Self::is_current_device(&serial_number, &unauthorized_device) {
+                    return;
+                }
+
+                let device_banner = match unauthorized_device.adb_device_connection().adb_await_authorization(Self::DEVICE_UNAUTHORIZED_RECONNECT_TIME_SECONDS as f64).await {
+                    Ok(Some(device_banner)) => device_banner,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        info!("Stopped waiting for authorization of device {}: {}", serial_number, err);
+                        if ADB_SERVER_INSTANCE.adb_devices_hashmap.remove_if(&serial_number, |_, adb_device| adb_device.as_ref().is_some_and(|adb_device| Arc::ptr_eq(adb_device, &unauthorized_device))).is_some() {
+                            Self::notify_devices_changed();
+                        }
+                        return;
+                    }
+                };
+
+                let mut authorized_device = AdbDevice::new(serial_number.clone(), unauthorized_device.adb_device_connection().clone(), AdbDeviceStatus::Available, *unauthorized_device.adb_device_type(), Duration::from_secs(Self::DEVICE_AVAILABLE_VERIFY_TIME_SECONDS));
+                authorized_device.set_device_details(unauthorized_device.device_path().map(str::to_string), Some(device_banner), unauthorized_device.transport_id());
+                authorized_device.set_auth_key_fingerprint(unauthorized_device.auth_key_fingerprint().cloned());
+
+                let is_promoted = match ADB_SERVER_INSTANCE.adb_devices_hashmap.get_mut(&serial_number) {
+                    Some(mut entry) if entry.value().as_ref().is_some_and(|adb_device| Arc::ptr_eq(adb_device, &unauthorized_device)) => {
+                        *entry.value_mut() = Some(Arc::new(authorized_device));
+                        true
+                    }
+                    _ => false,
+                };
+                if is_promoted {
+                    Self::notify_devices_changed();
+                    info!("Device {} authorized and connected successfully", serial_number);
+                    Self::execute_connect_actions(serial_number);
+                }
+                return;
+            }
+        });
+    }
+
+    fn is_current_device(serial_number: &str, adb_device: &Arc<AdbDevice>) -> bool {
+        ADB_SERVER_INSTANCE.adb_devices_hashmap.get(serial_number)
+            .is_some_and(|entry| entry.value().as_ref().is_some_and(|current_device| Arc::ptr_eq(current_device, adb_device)))
+    }
+
+    fn execute_connect_actions(serial_number: String) {
+        tokio::spawn(async move {
+            match ActionConfig::load() {
+                Ok(action_config) => {
+                    match execute_action(&action_config, &serial_number, CONNECT_EVENT) {
+                        Ok(()) => info!("Successfully executed connect actions for device {}", serial_number),
+                        Err(e) => error!("Failed to execute connect actions: {}", e),
+                    }
+                }
+                Err(e) => error!("Failed to load actions configuration: {}", e),
+            }
+        });
+    }
+
+    fn execute_disconnect_actions(serial_number: String) {
+        tokio::spawn(async move {
+            match ActionConfig::load() {
+                Ok(action_config) => {
+                    match execute_action(&action_config, &serial_number, DISCONNECT_EVENT) {
+                        Ok(()) => info!("Successfully executed disconnect actions for device {}", serial_number),
+                        Err(e) => error!("Failed to execute disconnect actions: {}", e),
+                    }
+                }
+                Err(e) => error!("Failed to load actions configuration: {}", e),
+            }
+        });
+    }
+
+    fn create_adb_device(serial_number: String, adb_device_connection: AdbDeviceConnection, adb_device_status: AdbDeviceStatus, device_type: AdbDeviceType, device_path: Option<String>, monitoring_interval: Duration) -> AdbDevice {
+        let device_banner = adb_device_connection.device_banner().cloned();
+        let auth_key_fingerprint = adb_device_connection.auth_key_fingerprint();
+        let mut adb_device = AdbDevice::new(serial_number, Arc::new(adb_device_connection), adb_device_status, device_type, monitoring_interval);
+        adb_device.set_device_details(device_path, device_banner, Self::next_transport_id());
+        adb_device.set_auth_key_fingerprint(auth_key_fingerprint);
+        adb_device
+    }
+
+    fn next_transport_id() -> u64 {
+        ADB_SERVER_INSTANCE.last_transport_id.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
     async fn monitor_connected_devices() {
         loop {
             if !ADB_SERVER_INSTANCE.adb_devices_hashmap.is_empty() {
@@ -496,23 +778,13 @@ impl AdbServer {
                                                 info!("Device {} disconnected", serial_number);
                                                 adb_device.close_device_gracefully().await;
                                                 adb_device_connection.close().await;
-                                                let serial_number_clone = serial_number.clone();
-                                                tokio::spawn(async move {
-                                                    match ActionConfig::load() {
-                                                        Ok(action_config) => {
-                                                            match execute_action(&action_config, &serial_number_clone, DISCONNECT_EVENT) {
-                                                                Ok(()) => info!("Successfully executed disconnect actions for device {}", serial_number_clone),
-                                                                Err(e) => error!("Failed to execute disconnect actions: {}", e),
-                                                            }
-                                                        }
-                                                        Err(e) => error!("Failed to load actions configuration: {}", e),
-                                                    }
-                                                });
+                                                Self::execute_disconnect_actions(serial_number.clone());
                                                 Some(serial_number.clone())
                                             }
                                         }
                                     }
-                                    AdbDeviceStatus::Unauthorized | AdbDeviceStatus::Offline(_) => {
+                                    AdbDeviceStatus::Unauthorized => None,
+                                    AdbDeviceStatus::Offline(_) => {
                                         info!("Attempting to reconnect to device {}", serial_number);
                                         Some(serial_number.clone())
                                     }
@@ -529,8 +801,11 @@ impl AdbServer {
                     .collect::<Vec<String>>()
                     .await;
 
-                for serial_number in serials_to_remove {
-                    ADB_SERVER_INSTANCE.adb_devices_hashmap.remove(&serial_number);
+                if !serials_to_remove.is_empty() {
+                    for serial_number in serials_to_remove {
+                        ADB_SERVER_INSTANCE.adb_devices_hashmap.remove(&serial_number);
+                    }
+                    Self::notify_devices_changed();
                 }
             }
             time::sleep(Duration::from_millis(Self::MONITORING_INTERVAL_MS)).await;
@@ -1,71 +1,110 @@
 use tokio::net::TcpStream;
 use crate::adb::errors::adb_connection_error::AdbConnectionError;
-use crate::adb::io::socket::{send_bytes, send_ok_with_response};
+use crate::adb::enums::adb_shell_packet_id::AdbShellPacketId;
+use crate::adb::io::socket::{send_bytes, send_fail_with_response, send_ok_with_response};
 use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::models::adb_shell_packet::AdbShellPacket;
+use crate::adb::models::adb_transaction_info::AdbTransactionInfo;
 use crate::adb::server::server::AdbServer;
-use crate::constants::{BINARY_SHELL_COMMAND, BINARY_SHELL_COMMAND_NULL, SHELL_BUGREPORT_COMMAND};
+use crate::constants::{ADBR_TIMEOUT_SHELL_ARG, SHELL_ARGS_SEPARATOR, SHELL_COMMAND, SHELL_V2_ARG, SHELL_V2_FEATURE, SHELL_WITH_ARGS_COMMAND};
 use crate::utils::utils::ensure_null_terminated;
 use tracing::warn;
 impl AdbServer {
-    const SHELL_COMMAND_TIMEOUT: f64 = 15.0;
-    const BUGREPORT_TIMEOUT: f64 = 100.0;
-    const SHELL_INPUT_OPERATION_TIME_OUT_SECONDS: f64 = 0.05;
-    const SHELL_COMMAND_TIME_OUT_SECONDS: f64 = 0.25;
+    const OPEN_SERVICE_TIMEOUT_SECONDS: f64 = 15.0;
+    const SHELL_FAILURE_EXIT_CODE: u8 = 1;
 
     pub async fn handle_shell_command(adb_device: &AdbDevice, socket: &mut TcpStream, shell_command: String) {
-        if let Err(err) = send_ok_with_response(socket, None).await {
-            warn!("Failed to send OK response to client: {}", err);
+        let (shell_command, operation_timeout_s) = Self::take_shell_timeout_arg(shell_command);
+        let is_shell_v2 = Self::is_shell_v2_command(&shell_command);
+        if is_shell_v2 && !adb_device.has_feature(SHELL_V2_FEATURE) {
+            if let Err(err) = send_fail_with_response(socket, Some("device doesn't support shell protocol".to_string())).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
             return;
         }
 
-        let command_bytes = shell_command.as_bytes();
-        match command_bytes {
-            BINARY_SHELL_COMMAND | BINARY_SHELL_COMMAND_NULL => {
-                Self::handle_interactive_shell(adb_device, socket, shell_command).await
-            }
-            _ => {
-                Self::handle_single_command(adb_device, socket, shell_command).await
-            }
-        }
-    }
+        let Some(transaction_info) = Self::open_device_service(adb_device, socket, shell_command).await else {
+            return;
+        };
 
-    async fn handle_single_command(adb_device: &AdbDevice, socket: &mut TcpStream, command: String) {
-        let timeout = Self::get_command_timeout(&command);
-        let command = ensure_null_terminated(command);
+        if let Err(err) = adb_device.adb_device_connection().relay_stream(&transaction_info, socket, operation_timeout_s).await {
+            warn!("Shell session failed: {}", err);
+            let error_msg = match (err, operation_timeout_s) {
+                (AdbConnectionError::Timeout, Some(timeout)) => format!("Command timed out after {} seconds\n", timeout),
+                (err, _) => format!("Command execution failed: {}\n", err),
+            };
 
-        if let Err(err) = adb_device.adb_device_connection()
-            .adb_shell_command(socket, command, Some(timeout)).await
-        {
-            let error_msg = match err {
-                AdbConnectionError::Timeout => format!("Command timed out after {} seconds\n", timeout),
-                _ => format!("Command execution failed: {}\n", err),
+            let error_response = if is_shell_v2 {
+                let mut error_packets = AdbShellPacket::new(AdbShellPacketId::Stderr, error_msg.into_bytes()).pack_packet();
+                error_packets.extend(AdbShellPacket::exit(Self::SHELL_FAILURE_EXIT_CODE).pack_packet());
+                error_packets
+            } else {
+                error_msg.into_bytes()
             };
 
-            if let Err(err) = send_bytes(socket, error_msg.as_bytes()).await {
+            if let Err(err) = send_bytes(socket, &error_response).await {
                 warn!("Failed to send error message to client: {}", err);
             }
         }
     }
 
-    async fn handle_interactive_shell(adb_device: &AdbDevice, socket: &mut TcpStream, shell_command: String) {
-        if let Err(err) = adb_device.adb_device_connection()
-            .open_shell_session(socket, shell_command, Some(Self::SHELL_COMMAND_TIME_OUT_SECONDS), Some(Self::SHELL_INPUT_OPERATION_TIME_OUT_SECONDS)).await
-        {
-            let error_msg = match err {
-                AdbConnectionError::Timeout => "Interactive shell timed out".to_string(),
-                _ => format!("Interactive shell session failed: {}\n", err),
-            };
+    pub async fn handle_exec_command(adb_device: &AdbDevice, socket: &mut TcpStream, exec_command: String) {
+        let Some(transaction_info) = Self::open_device_service(adb_device, socket, exec_command).await else {
+            return;
+        };
 
-            if let Err(err) = send_bytes(socket, error_msg.as_bytes()).await {
-                warn!("Failed to send error message to client: {}", err);
-            }
+        if let Err(err) = adb_device.adb_device_connection().relay_stream(&transaction_info, socket, None).await {
+            warn!("Exec session failed: {}", err);
         }
     }
 
-    fn get_command_timeout(command: &str) -> f64 {
-        match command {
-            cmd if cmd.starts_with(SHELL_BUGREPORT_COMMAND) => Self::BUGREPORT_TIMEOUT,
-            _ => Self::SHELL_COMMAND_TIMEOUT,
+    pub async fn open_device_service(adb_device: &AdbDevice, socket: &mut TcpStream, service: String) -> Option<AdbTransactionInfo> {
+        let service = ensure_null_terminated(service);
+        let transaction_info = match adb_device.adb_device_connection().open_stream(&service, Some(Self::OPEN_SERVICE_TIMEOUT_SECONDS)).await {
+            Ok(transaction_info) => transaction_info,
+            Err(err) => {
+                if let Err(err) = send_fail_with_response(socket, Some(format!("Failed to open {}: {}", service.trim_end_matches('\0'), err))).await {
+                    warn!("Failed to send FAIL response to client: {}", err);
+                }
+                return None;
+            }
+        };
+
+        if let Err(err) = send_ok_with_response(socket, None).await {
+            warn!("Failed to send OK response to client: {}", err);
+            return None;
         }
+
+        Some(transaction_info)
+    }
+
+    fn take_shell_timeout_arg(shell_command: String) -> (String, Option<f64>) {
+        let Some((shell_args, command)) = shell_command.strip_prefix(SHELL_WITH_ARGS_COMMAND).and_then(|service| service.split_once(':')) else {
+            return (shell_command, None);
+        };
+
+        let mut operation_timeout_s = None;
+        let device_shell_args: Vec<&str> = shell_args.split(SHELL_ARGS_SEPARATOR)
+            .filter(|shell_arg| match shell_arg.strip_prefix(ADBR_TIMEOUT_SHELL_ARG) {
+                Some(timeout) => {
+                    operation_timeout_s = timeout.parse::<f64>().ok().filter(|timeout| *timeout > 0.0);
+                    false
+                }
+                None => true,
+            })
+            .collect();
+
+        let shell_command = if device_shell_args.is_empty() {
+            format!("{}{}", SHELL_COMMAND, command)
+        } else {
+            format!("{}{}:{}", SHELL_WITH_ARGS_COMMAND, device_shell_args.join(&SHELL_ARGS_SEPARATOR.to_string()), command)
+        };
+        (shell_command, operation_timeout_s)
+    }
+
+    fn is_shell_v2_command(shell_command: &str) -> bool {
+        shell_command.strip_prefix(SHELL_WITH_ARGS_COMMAND)
+            .and_then(|service| service.split_once(':'))
+            .is_some_and(|(shell_args, _)| shell_args.split(SHELL_ARGS_SEPARATOR).any(|shell_arg| shell_arg == SHELL_V2_ARG))
     }
 }
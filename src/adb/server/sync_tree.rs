@@ -0,0 +1,54 @@
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use crate::adb::enums::adb_sync_tree_event::AdbSyncTreeEvent;
+use crate::adb::io::socket::{send_fail_with_response, send_full_response, send_ok_with_response};
+use crate::adb::models::adb_device::AdbDevice;
+use crate::adb::server::server::AdbServer;
+use crate::constants::{ADBR_SYNC_COMMAND, NULL_TERMINATOR};
+use crate::utils::utils::format_response_with_size;
+
+impl AdbServer {
+    const SYNC_TREE_OPERATION_TIMEOUT_SECONDS: f64 = 120.0;
+
+    pub async fn handle_adbr_sync_command(adb_device: &AdbDevice, socket: &mut TcpStream, sync_command: String) {
+        let sync_dirs = sync_command.strip_prefix(ADBR_SYNC_COMMAND)
+            .and_then(|sync_dirs| sync_dirs.trim_end_matches(NULL_TERMINATOR).split_once(NULL_TERMINATOR))
+            .filter(|(local_dir, remote_dir)| !local_dir.is_empty() && !remote_dir.is_empty());
+        let Some((local_dir, remote_dir)) = sync_dirs else {
+            if let Err(err) = send_fail_with_response(socket, Some(format!("Expected {}<local dir>\\0<remote dir>", ADBR_SYNC_COMMAND))).await {
+                warn!("Failed to send FAIL response to client: {}", err);
+            }
+            return;
+        };
+
+        if let Err(err) = send_ok_with_response(socket, None).await {
+            warn!("Failed to send OK response to client: {}", err);
+            return;
+        }
+
+        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let sync_tree = adb_device.adb_device_connection()
+            .adb_sync_tree(local_dir, remote_dir, event_sender, Some(Self::SYNC_TREE_OPERATION_TIMEOUT_SECONDS));
+        let relay_events = async {
+            while let Some(event) = event_receiver.recv().await {
+                if let Err(err) = send_full_response(socket, format_response_with_size(event.to_message())).await {
+                    warn!("Failed to send sync progress to client: {}", err);
+                    return;
+                }
+            }
+        };
+        let (sync_result, _) = tokio::join!(sync_tree, relay_events);
+
+        match sync_result {
+            Ok(()) => info!("Synced {} to {} on {}", local_dir, remote_dir, adb_device.device_serial_number()),
+            Err(err) => {
+                error!("Sync of {} to {} on {} failed: {}", local_dir, remote_dir, adb_device.device_serial_number(), err);
+                let fail_event = AdbSyncTreeEvent::Failed(err.to_string());
+                if let Err(err) = send_full_response(socket, format_response_with_size(fail_event.to_message())).await {
+                    warn!("Failed to send sync failure to client: {}", err);
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::adb::errors::adb_server_error::AdbServerError;
 use crate::adb::io::socket::{send_fail_with_response, send_ok_with_response};
 use crate::adb::models::adb_device::AdbDevice;
 use crate::adb::server::server::{ADB_SERVER_INSTANCE, AdbServer};
+use crate::constants::{HOST_PREFIX, HOST_SERIAL_COMMAND, HOST_SERIAL_SERVICES};
 
 impl AdbServer {
     pub async fn execute_transport_command(socket: &mut TcpStream, device_transport: AdbDeviceTransport) -> Option<Arc<AdbDevice>> {
@@ -28,7 +29,7 @@ impl AdbServer {
         }
     }
 
-    fn get_adb_device_by_device_transport(device_transport: AdbDeviceTransport) -> Result<Arc<AdbDevice>, AdbServerError> {
+    pub fn get_adb_device_by_device_transport(device_transport: AdbDeviceTransport) -> Result<Arc<AdbDevice>, AdbServerError> {
         let all_serials_by_transport = Self::get_serials_number_by_device_transport(device_transport.clone());
         let chosen_serial_number = device_transport.get_serial();
 
@@ -48,7 +49,19 @@ impl AdbServer {
             }
         }
     }
-    fn get_adb_device_by_serial(serial: &str) -> Result<Arc<AdbDevice>, AdbServerError> {
+    pub fn parse_host_serial_command(command: &str) -> Option<(String, String)> {
+        let serial_and_service = command.strip_prefix(HOST_SERIAL_COMMAND)?;
+        let service_index = HOST_SERIAL_SERVICES.iter()
+            .filter_map(|service| serial_and_service.find(&format!(":{}", service)))
+            .filter(|index| *index > 0)
+            .min()?;
+
+        let serial_number = serial_and_service[..service_index].to_string();
+        let service = serial_and_service[service_index + 1..].to_string();
+        Some((serial_number, format!("{}{}", HOST_PREFIX, service)))
+    }
+
+    pub fn get_adb_device_by_serial(serial: &str) -> Result<Arc<AdbDevice>, AdbServerError> {
         match ADB_SERVER_INSTANCE.adb_devices_hashmap.get(serial) {
             Some(entry) => {
                 entry
@@ -85,6 +98,9 @@ impl AdbServer {
                             if adb_device.is_usb_device() && serial_number == device_serial_number => {
                                 Some(device_serial_number.clone())
                             }
+                            AdbDeviceTransport::Serial(serial_number) if serial_number == device_serial_number => {
+                                Some(device_serial_number.clone())
+                            }
                             _ => None,
                         }
                     } else {
@@ -5,6 +5,41 @@ pub const FAIL: &str = "FAIL";
 pub const B_FAIL: &[u8] = b"FAIL";
 
 pub const ADB_SERVER_VERSION: u32 = 0x01000000;
+pub const ADB_PROTOCOL_VERSION: u32 = 0x01000001;
+pub const ADB_VERSION_SKIP_CHECKSUM: u32 = 0x01000001;
+
+pub const SHELL_V2_FEATURE: &str = "shell_v2";
+pub const TRACK_APP_FEATURE: &str = "track_app";
+pub const ABB_FEATURE: &str = "abb";
+pub const ABB_EXEC_FEATURE: &str = "abb_exec";
+pub const CMD_FEATURE: &str = "cmd";
+pub const STAT_V2_FEATURE: &str = "stat_v2";
+pub const LS_V2_FEATURE: &str = "ls_v2";
+pub const SENDRECV_V2_FEATURE: &str = "sendrecv_v2";
+pub const SENDRECV_V2_BROTLI_FEATURE: &str = "sendrecv_v2_brotli";
+pub const SENDRECV_V2_LZ4_FEATURE: &str = "sendrecv_v2_lz4";
+pub const SENDRECV_V2_ZSTD_FEATURE: &str = "sendrecv_v2_zstd";
+pub const SENDRECV_V2_DRY_RUN_SEND_FEATURE: &str = "sendrecv_v2_dry_run_send";
+
+pub const ADB_SERVER_FEATURES: &[&str] = &[
+    SHELL_V2_FEATURE,
+    TRACK_APP_FEATURE,
+    ABB_FEATURE,
+    ABB_EXEC_FEATURE,
+    CMD_FEATURE,
+    STAT_V2_FEATURE,
+    LS_V2_FEATURE,
+    SENDRECV_V2_FEATURE,
+    SENDRECV_V2_BROTLI_FEATURE,
+    SENDRECV_V2_LZ4_FEATURE,
+    SENDRECV_V2_ZSTD_FEATURE,
+    SENDRECV_V2_DRY_RUN_SEND_FEATURE,
+    "fixed_push_mkdir",
+    "fixed_push_symlink_timestamp",
+    "apex",
+    "remount_shell",
+    "push_sync",
+];
 
 pub const MAX_ADB_DATA: u32 = 1024 * 1024;
 
@@ -13,16 +48,30 @@ pub const DEFAULT_ADB_SERVER_PORT: u16 = 5037;
 pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024; // 64K
 pub const ADB_MESSAGE_SIZE: usize = 24;
 
-pub const BINARY_SHELL_COMMAND: &[u8] = b"shell:";
-pub const BINARY_SHELL_COMMAND_NULL: &[u8] = b"shell:\0";
 pub const SHELL_COMMAND: &str = "shell:";
+pub const SHELL_WITH_ARGS_COMMAND: &str = "shell,";
+pub const SHELL_ARGS_SEPARATOR: char = ',';
+pub const SHELL_V2_ARG: &str = "v2";
+pub const ADBR_TIMEOUT_SHELL_ARG: &str = "adbr_timeout=";
+pub const EXEC_COMMAND: &str = "exec:";
+pub const TRACK_JDWP_COMMAND: &str = "track-jdwp";
+pub const TRACK_APP_COMMAND: &str = "track-app";
+pub const ABB_COMMAND: &str = "abb:";
+pub const ABB_EXEC_COMMAND: &str = "abb_exec:";
+pub const ADBR_INSTALL_COMMAND: &str = "adbr:install:";
+pub const ADBR_INSTALL_WITH_ARGS_COMMAND: &str = "adbr:install,";
+pub const ADBR_INSTALL_ARGS_SEPARATOR: char = ',';
+pub const ADBR_PULL_COMMAND: &str = "adbr:pull:";
+pub const ADBR_SYNC_COMMAND: &str = "adbr:sync:";
 
 pub const ADB_PRIVATE_KEY_FILE: &'static str = "adbkey";
 pub const ADB_PUBLIC_KEY_FILE: &'static str = "adbkey.pub";
+pub const ADB_RSA_KEY_BITS: usize = 2048;
+pub const ADB_VENDOR_KEYS_ENV: &str = "ADB_VENDOR_KEYS";
+pub const ADB_VENDOR_KEY_EXTENSION: &str = "adb_key";
 pub const HOST: &str = "host::";
 
 pub const ZERO: u32 = 0;
-pub const ONE: u32 = 1;
 pub const NULL_TERMINATOR: char = '\0';
 pub const EXIT_FAILURE: i32 = 1;
 
@@ -41,14 +90,38 @@ pub const REVERSE_FORWARD_LIST_COMMAND: &str = "reverse:list-forward";
 pub const HOST_SERIALNO_COMMAND: &str = "host:get-serialno";
 pub const HOST_GET_DEVPATH_COMMAND: &str = "host:get-devpath";
 pub const HOST_GET_STATE_COMMAND: &str = "host:get-state";
+pub const HOST_AUTH_INFO_COMMAND: &str = "host:auth-info";
+pub const HOST_FEATURES_COMMAND: &str = "host:features";
+pub const HOST_HOST_FEATURES_COMMAND: &str = "host:host-features";
+pub const HOST_SERIAL_COMMAND: &str = "host-serial:";
+pub const HOST_CONNECT_COMMAND: &str = "host:connect:";
+pub const HOST_DISCONNECT_COMMAND: &str = "host:disconnect:";
+pub const DEFAULT_ADB_TCP_PORT: u16 = 5555;
+pub const EMULATOR_SERIAL_PREFIX: &str = "emulator-";
+pub const HOST_PREFIX: &str = "host:";
+pub const HOST_SERIAL_SERVICES: &[&str] = &[
+    "features",
+    "get-state",
+    "auth-info",
+    "get-serialno",
+    "get-devpath",
+    "forward:",
+    "killforward:",
+    "killforward-all",
+    "list-forward",
+];
 
 pub const SYNC_COMMAND: &str = "sync:";
+pub const SYNC_ID_SIZE: usize = 4;
 
 pub const SYNC_SEND_COMMAND: &[u8] = b"SEND";
 pub const SYNC_SEND_COMMAND_STR: &str = "SEND";
 
 pub const SYNC_DATA_COMMAND: &[u8] = b"DATA";
 pub const SYNC_DATA_COMMAND_STR: &str = "DATA";
+pub const SYNC_DATA_MAX_SIZE: usize = 64 * 1024;
+pub const SYNC_FILE_TYPE_MASK: u32 = 0o170000;
+pub const SYNC_FILE_TYPE_REGULAR: u32 = 0o100000;
 
 pub const SYNC_DONE_COMMAND: &[u8] = b"DONE";
 pub const SYNC_DONE_COMMAND_STR: &str = "DONE";
@@ -69,10 +142,34 @@ pub const SYNC_DENT_COMMAND: &[u8] = b"DENT";
 pub const SYNC_DENT_COMMAND_STR: &str = "DENT";
 pub const DENT_HEADER_SIZE: usize = 16;
 pub const DENT_NAME_LENGTH_SIZE: usize = 4;
-pub const DENT_MIN_SIZE: usize = DENT_HEADER_SIZE + DENT_NAME_LENGTH_SIZE;
+
+pub const SYNC_STAT_V2_COMMAND: &[u8] = b"STA2";
+pub const SYNC_STAT_V2_COMMAND_STR: &str = "STA2";
+
+pub const SYNC_LSTAT_V2_COMMAND: &[u8] = b"LST2";
+pub const SYNC_LSTAT_V2_COMMAND_STR: &str = "LST2";
+
+pub const SYNC_LIST_V2_COMMAND: &[u8] = b"LIS2";
+pub const SYNC_LIST_V2_COMMAND_STR: &str = "LIS2";
+
+pub const SYNC_DENT_V2_COMMAND: &[u8] = b"DNT2";
+pub const DENT_V2_HEADER_SIZE: usize = 72;
+
+pub const SYNC_SEND_V2_COMMAND: &[u8] = b"SND2";
+pub const SYNC_SEND_V2_COMMAND_STR: &str = "SND2";
+
+pub const SYNC_RECV_V2_COMMAND: &[u8] = b"RCV2";
+pub const SYNC_RECV_V2_COMMAND_STR: &str = "RCV2";
+
+pub const SYNC_FLAG_BROTLI: u32 = 1;
+pub const SYNC_FLAG_LZ4: u32 = 2;
+pub const SYNC_FLAG_ZSTD: u32 = 4;
+pub const SYNC_FLAG_DRY_RUN: u32 = 0x8000_0000;
 
 pub const HOST_VERSION_COMMAND: &str = "host:version";
+pub const HOST_DEVICES_LONG_COMMAND: &str = "host:devices-l";
 pub const HOST_DEVICES_COMMAND: &str = "host:devices";
+pub const HOST_TRACK_DEVICES_COMMAND: &str = "host:track-devices";
 pub const REBOOT_COMMAND: &str = "reboot:";
 pub const REMOUNT_COMMAND: &str = "remount:";
 pub const ROOT_COMMAND: &str = "root:";
@@ -81,9 +178,6 @@ pub const UNROOT_COMMAND: &str = "unroot:";
 pub const DISABLE_VERITY_COMMAND: &str = "disable-verity:";
 pub const ENABLE_VERITY_COMMAND: &str = "enable-verity:";
 
-pub const SHELL_BUGREPORT_COMMAND: &str = "shell:bugreportz";
-pub const SYNC_RECV_GET_DATA_TIME_SECONDS: f64 = 1.0;
-
 
 pub const NO_REBIND_PORT_PREFIX: &str = "norebind:";
 
@@ -97,6 +191,7 @@ pub const WRTE_CODE: u32 = 0x45545257;
 pub const ABSTRACT_SOCKET_PREFIX: &str = "\0";
 pub const RESERVED_SOCKET_PREFIX: &str = "/reserved/";
 pub const DEV_SOCKET_PREFIX: &str = "/dev/";
+pub const USB_DEVICE_PATH_PREFIX: &str = "usb:";
 
 pub const CONNECT_EVENT: &str = "connect";
 pub const DISCONNECT_EVENT: &str = "disconnect";
\ No newline at end of file
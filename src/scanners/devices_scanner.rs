@@ -1,36 +1,45 @@
-use std::future::Future;
-use std::pin::Pin;
 use std::sync::Arc;
 
+use dashmap::DashSet;
+use futures::future::join_all;
+
 use crate::scanners::devices_scanner_impl::DevicesScannerImpl;
-use crate::scanners::devices_scanner_trait::DevicesScannerTrait;
+use crate::scanners::devices_scanner_trait::{DevicesScannerTrait, IsNewDeviceCallback, OnFindDeviceCallback, OnLostDeviceCallback};
+use crate::scanners::emulator_devices_scanner::EmulatorDevicesScanner;
+use crate::scanners::tcp_devices_scanner::TcpDevicesScanner;
 use crate::scanners::usb_devices_scanner::UsbDevicesScanner;
 use crate::transport::enums::interface_type::InterfaceType;
-use crate::transport::transport::Transport;
 
 pub struct DevicesScanner {
     scanners: Vec<DevicesScannerImpl>,
 }
 
 impl DevicesScanner {
-    pub fn new() -> Self {
-        let scanners = vec![DevicesScannerImpl::UsbScanner(UsbDevicesScanner::new())];
+    pub fn new(tcp_devices_registry: Arc<DashSet<String>>) -> Self {
+        let scanners = vec![
+            DevicesScannerImpl::Usb(UsbDevicesScanner::new()),
+            DevicesScannerImpl::Tcp(TcpDevicesScanner::new(tcp_devices_registry)),
+            DevicesScannerImpl::Emulator(EmulatorDevicesScanner::new()),
+        ];
         DevicesScanner { scanners }
     }
 
     pub async fn start_scanning(
         &mut self,
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        on_lost_device: OnLostDeviceCallback
     ) {
-        for scanner in &mut self.scanners {
+        let scanning_tasks = self.scanners.iter_mut().map(|scanner| {
             scanner.start_scanning(
                 device_type,
                 is_new_device.clone(),
-                on_find_device.clone()
-            ).await;
-        }
+                on_find_device.clone(),
+                on_lost_device.clone()
+            )
+        });
+        join_all(scanning_tasks).await;
     }
 
     #[allow(dead_code)]
@@ -1,8 +1,9 @@
+use crate::scanners::emulator_devices_scanner::EmulatorDevicesScanner;
 use crate::scanners::tcp_devices_scanner::TcpDevicesScanner;
 use crate::scanners::usb_devices_scanner::UsbDevicesScanner;
 
-#[allow(dead_code)]
 pub enum DevicesScannerImpl {
-    UsbScanner(UsbDevicesScanner),
-    TcpScanner(TcpDevicesScanner),
+    Usb(UsbDevicesScanner),
+    Tcp(TcpDevicesScanner),
+    Emulator(EmulatorDevicesScanner),
 }
\ No newline at end of file
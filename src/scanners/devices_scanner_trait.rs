@@ -8,12 +8,17 @@ use crate::transport::transport::Transport;
 pub const SCANNING_CHANNEL_CAPACITY: usize = 1;
 pub const SCANNING_INTERVAL_MS: u64 = 1000;
 
+pub type IsNewDeviceCallback = Arc<dyn Fn(String) -> bool + Send + Sync>;
+pub type OnFindDeviceCallback = Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+pub type OnLostDeviceCallback = Arc<dyn Fn(String) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
 pub trait DevicesScannerTrait {
     async fn start_scanning(
         &mut self,
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        on_lost_device: OnLostDeviceCallback
     );
     fn stop_scanning(&mut self);
 }
@@ -22,23 +27,28 @@ impl DevicesScannerTrait for DevicesScannerImpl {
     async fn start_scanning(
         &mut self,
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        on_lost_device: OnLostDeviceCallback
     ) {
         match self {
-            DevicesScannerImpl::UsbScanner(scanner) => {
-                scanner.start_scanning(device_type, is_new_device, on_find_device).await
+            DevicesScannerImpl::Usb(scanner) => {
+                scanner.start_scanning(device_type, is_new_device, on_find_device, on_lost_device).await
+            },
+            DevicesScannerImpl::Tcp(scanner) => {
+                scanner.start_scanning(device_type, is_new_device, on_find_device, on_lost_device).await
             },
-            DevicesScannerImpl::TcpScanner(scanner) => {
-                scanner.start_scanning(device_type, is_new_device, on_find_device).await
+            DevicesScannerImpl::Emulator(scanner) => {
+                scanner.start_scanning(device_type, is_new_device, on_find_device, on_lost_device).await
             },
         }
     }
 
     fn stop_scanning(&mut self) {
         match self {
-            DevicesScannerImpl::UsbScanner(scanner) => scanner.stop_scanning(),
-            DevicesScannerImpl::TcpScanner(scanner) => scanner.stop_scanning(),
+            DevicesScannerImpl::Usb(scanner) => scanner.stop_scanning(),
+            DevicesScannerImpl::Tcp(scanner) => scanner.stop_scanning(),
+            DevicesScannerImpl::Emulator(scanner) => scanner.stop_scanning(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::select;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, timeout};
+
+use crate::constants::{EMULATOR_SERIAL_PREFIX, LOCAL_IP};
+use crate::scanners::devices_scanner_trait::{DevicesScannerTrait, IsNewDeviceCallback, OnFindDeviceCallback, OnLostDeviceCallback, SCANNING_CHANNEL_CAPACITY, SCANNING_INTERVAL_MS};
+use crate::transport::enums::interface_type::InterfaceType;
+use crate::transport::tcp_transport::TcpTransport;
+
+const EMULATOR_CONSOLE_PORT_START: u16 = 5554;
+const EMULATOR_MAX_COUNT: u16 = 16;
+const PROBE_EMULATOR_TIMEOUT_MS: u64 = 100;
+
+pub struct EmulatorDevicesScanner {
+    stop_scanning_tx: Option<mpsc::Sender<()>>,
+}
+
+impl DevicesScannerTrait for EmulatorDevicesScanner {
+    async fn start_scanning(
+        &mut self,
+        _device_type: InterfaceType,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        _on_lost_device: OnLostDeviceCallback
+    ) {
+        let (stop_scanning_tx, mut stop_scanning_rx) = mpsc::channel(SCANNING_CHANNEL_CAPACITY);
+        self.stop_scanning_tx = Some(stop_scanning_tx);
+
+        loop {
+            select! {
+                _ = Self::_start_scanning(EMULATOR_CONSOLE_PORT_START, is_new_device.clone(), on_find_device.clone()) => {
+                }
+                _ = stop_scanning_rx.recv() => {
+                    break;
+                }
+            }
+            sleep(Duration::from_millis(SCANNING_INTERVAL_MS)).await;
+        }
+    }
+
+    fn stop_scanning(&mut self) {
+        if let Some(stop_scanning_tx) = self.stop_scanning_tx.take() {
+            let _ = stop_scanning_tx.try_send(());
+        }
+    }
+}
+
+impl EmulatorDevicesScanner {
+    pub fn new() -> EmulatorDevicesScanner {
+        Self {
+            stop_scanning_tx: None,
+        }
+    }
+
+    async fn _start_scanning(
+        console_port_start: u16,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+    ) {
+        for emulator_index in 0..EMULATOR_MAX_COUNT {
+            let Some(console_port) = console_port_start.checked_add(emulator_index * 2) else {
+                break;
+            };
+            let Some(adb_port) = console_port.checked_add(1) else {
+                break;
+            };
+            let serial_number = format!("{}{}", EMULATOR_SERIAL_PREFIX, console_port);
+
+            if !is_new_device(serial_number.clone()) {
+                continue;
+            }
+
+            let device_address = format!("{}:{}", LOCAL_IP, adb_port);
+            if Self::is_adb_port_open(&device_address).await {
+                let transport = TcpTransport::new(device_address);
+                on_find_device(serial_number, Box::new(transport), InterfaceType::android_tcp()).await;
+            }
+        }
+    }
+
+    async fn is_adb_port_open(device_address: &str) -> bool {
+        matches!(
+            timeout(Duration::from_millis(PROBE_EMULATOR_TIMEOUT_MS), TcpStream::connect(device_address)).await,
+            Ok(Ok(_))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::adb::enums::adb_device_transport::AdbDeviceTransport;
+    use crate::adb::enums::adb_device_type::AdbDeviceType;
+    use crate::adb::server::connect::tests::spawn_fake_adbd;
+    use crate::adb::server::server::{ADB_SERVER_INSTANCE, AdbServer};
+    use crate::scanners::devices_scanner_trait::IsNewDeviceCallback;
+
+    fn recording_on_find_device(found_serials: Arc<Mutex<Vec<String>>>) -> OnFindDeviceCallback {
+        Arc::new(move |serial_number, _transport, _interface_type| {
+            let found_serials = found_serials.clone();
+            Box::pin(async move { found_serials.lock().unwrap().push(serial_number) })
+        })
+    }
+
+    async fn bind_fake_adbd() -> (TcpListener, u16) {
+        let listener = TcpListener::bind(format!("{}:0", LOCAL_IP)).await.unwrap();
+        let adb_port = listener.local_addr().unwrap().port();
+        (listener, adb_port)
+    }
+
+    #[tokio::test]
+    async fn scan_finds_emulator_listening_on_adb_port() {
+        let (_listener, adb_port) = bind_fake_adbd().await;
+        let console_port_start = adb_port - 1;
+        let found_serials = Arc::new(Mutex::new(Vec::new()));
+        let is_new_device: IsNewDeviceCallback = Arc::new(|_| true);
+
+        EmulatorDevicesScanner::_start_scanning(console_port_start, is_new_device, recording_on_find_device(found_serials.clone())).await;
+
+        let expected_serial = format!("{}{}", EMULATOR_SERIAL_PREFIX, console_port_start);
+        assert!(found_serials.lock().unwrap().contains(&expected_serial));
+    }
+
+    #[tokio::test]
+    async fn scan_skips_known_emulators() {
+        let (_listener, adb_port) = bind_fake_adbd().await;
+        let console_port_start = adb_port - 1;
+        let known_serial = format!("{}{}", EMULATOR_SERIAL_PREFIX, console_port_start);
+        let found_serials = Arc::new(Mutex::new(Vec::new()));
+        let is_new_device: IsNewDeviceCallback = {
+            let known_serial = known_serial.clone();
+            Arc::new(move |serial_number| serial_number != known_serial)
+        };
+
+        EmulatorDevicesScanner::_start_scanning(console_port_start, is_new_device, recording_on_find_device(found_serials.clone())).await;
+
+        assert!(!found_serials.lock().unwrap().contains(&known_serial));
+    }
+
+    #[tokio::test]
+    async fn scan_ignores_console_port() {
+        let (_listener, console_port) = bind_fake_adbd().await;
+        let found_serials = Arc::new(Mutex::new(Vec::new()));
+        let is_new_device: IsNewDeviceCallback = Arc::new(|_| true);
+
+        EmulatorDevicesScanner::_start_scanning(console_port, is_new_device, recording_on_find_device(found_serials.clone())).await;
+
+        let console_serial = format!("{}{}", EMULATOR_SERIAL_PREFIX, console_port);
+        assert!(!found_serials.lock().unwrap().contains(&console_serial));
+    }
+
+    #[tokio::test]
+    async fn scan_connects_emulator_and_resolves_it_as_local_transport() {
+        let device_address = spawn_fake_adbd(true).await;
+        let adb_port: u16 = device_address.rsplit_once(':').unwrap().1.parse().unwrap();
+        let console_port_start = adb_port - 1;
+        let serial_number = format!("{}{}", EMULATOR_SERIAL_PREFIX, console_port_start);
+        let is_new_device: IsNewDeviceCallback = {
+            let serial_number = serial_number.clone();
+            Arc::new(move |scanned_serial| scanned_serial == serial_number && !ADB_SERVER_INSTANCE.adb_devices_hashmap.contains_key(&scanned_serial))
+        };
+        let on_find_device: OnFindDeviceCallback = Arc::new(|serial_number, transport, interface_type| Box::pin(AdbServer::on_find_device(serial_number, transport, interface_type)));
+
+        EmulatorDevicesScanner::_start_scanning(console_port_start, is_new_device, on_find_device).await;
+
+        let adb_device = AdbServer::get_adb_device_by_serial(&serial_number).unwrap();
+        assert!(matches!(adb_device.adb_device_type(), AdbDeviceType::Emulator));
+        let local_device = AdbServer::get_adb_device_by_device_transport(AdbDeviceTransport::EmulatorAny).unwrap();
+        assert!(Arc::ptr_eq(&adb_device, &local_device));
+        let emulator_device = AdbServer::get_adb_device_by_device_transport(AdbDeviceTransport::emulator(&serial_number)).unwrap();
+        assert!(Arc::ptr_eq(&adb_device, &emulator_device));
+    }
+}
@@ -2,4 +2,5 @@ pub mod devices_scanner;
 mod devices_scanner_trait;
 mod usb_devices_scanner;
 mod tcp_devices_scanner;
+mod emulator_devices_scanner;
 mod devices_scanner_impl;
\ No newline at end of file
@@ -1,28 +1,70 @@
-use std::future::Future;
-use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::scanners::devices_scanner_trait::DevicesScannerTrait;
+use dashmap::DashSet;
+use tokio::select;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+use crate::scanners::devices_scanner_trait::{DevicesScannerTrait, IsNewDeviceCallback, OnFindDeviceCallback, OnLostDeviceCallback, SCANNING_CHANNEL_CAPACITY, SCANNING_INTERVAL_MS};
 use crate::transport::enums::interface_type::InterfaceType;
-use crate::transport::transport::Transport;
+use crate::transport::tcp_transport::TcpTransport;
 
-#[allow(dead_code)]
 pub struct TcpDevicesScanner {
-    continue_scanning: bool,
+    stop_scanning_tx: Option<mpsc::Sender<()>>,
+    tcp_devices_registry: Arc<DashSet<String>>,
 }
 
-#[allow(dead_code)]
 impl DevicesScannerTrait for TcpDevicesScanner {
     async fn start_scanning(
         &mut self,
         _device_type: InterfaceType,
-        _is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        _on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>
-    ){
-        todo!()
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        _on_lost_device: OnLostDeviceCallback
+    ) {
+        let (stop_scanning_tx, mut stop_scanning_rx) = mpsc::channel(SCANNING_CHANNEL_CAPACITY);
+        self.stop_scanning_tx = Some(stop_scanning_tx);
+
+        loop {
+            select! {
+                _ = Self::_start_scanning(self.tcp_devices_registry.clone(), is_new_device.clone(), on_find_device.clone()) => {
+                }
+                _ = stop_scanning_rx.recv() => {
+                    break;
+                }
+            }
+            sleep(Duration::from_millis(SCANNING_INTERVAL_MS)).await;
+        }
     }
 
     fn stop_scanning(&mut self) {
-        todo!()
+        if let Some(stop_scanning_tx) = self.stop_scanning_tx.take() {
+            let _ = stop_scanning_tx.try_send(());
+        }
+    }
+}
+
+impl TcpDevicesScanner {
+    pub fn new(tcp_devices_registry: Arc<DashSet<String>>) -> TcpDevicesScanner {
+        Self {
+            stop_scanning_tx: None,
+            tcp_devices_registry,
+        }
     }
-}
\ No newline at end of file
+
+    async fn _start_scanning(
+        tcp_devices_registry: Arc<DashSet<String>>,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+    ) {
+        let device_addresses: Vec<String> = tcp_devices_registry.iter().map(|address| address.clone()).collect();
+
+        for device_address in device_addresses {
+            if is_new_device(device_address.clone()) {
+                let transport = TcpTransport::new(device_address.clone());
+                on_find_device(device_address, Box::new(transport), InterfaceType::android_tcp()).await;
+            }
+        }
+    }
+}
@@ -1,34 +1,64 @@
-use core::pin::Pin;
 use std::error::Error;
-use std::future::Future;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
 use tokio::sync::mpsc;
 use std::time::{Duration};
-use rusb::{Context, Device, UsbContext};
-use crate::scanners::devices_scanner_trait::{DevicesScannerTrait, SCANNING_CHANNEL_CAPACITY, SCANNING_INTERVAL_MS};
-use crate::transport::transport::Transport;
+use dashmap::DashMap;
+use rusb::{Context, Device, Hotplug, HotplugBuilder, UsbContext};
+use crate::scanners::devices_scanner_trait::{DevicesScannerTrait, IsNewDeviceCallback, OnFindDeviceCallback, OnLostDeviceCallback, SCANNING_CHANNEL_CAPACITY, SCANNING_INTERVAL_MS};
 use crate::transport::usb_transport::UsbTransport;
 use tokio::select;
-use tokio::time::{sleep, timeout};
-use tracing::error;
+use tokio::time::{interval, sleep, timeout};
+use tracing::{debug, error, info, warn};
 use crate::transport::enums::interface_type::InterfaceType;
+use crate::constants::USB_DEVICE_PATH_PREFIX;
 
 const HANDLE_DEVICE_TIMEOUT_SECONDS: u64 = 10;
+const HOTPLUG_EVENTS_TIMEOUT_MS: u64 = 500;
+
+type AttachedUsbDevices = DashMap<(u8, u8), (String, Device<Context>)>;
+type PendingUsbDevices = DashMap<(u8, u8), Device<Context>>;
 
 pub struct UsbDevicesScanner {
     stop_scanning_tx: Option<mpsc::Sender<()>>,
 }
 
+enum UsbHotplugEvent {
+    Arrived(Device<Context>),
+    Left(Device<Context>),
+}
+
+struct UsbHotplugHandler {
+    events_tx: mpsc::UnboundedSender<UsbHotplugEvent>,
+}
+
+impl Hotplug<Context> for UsbHotplugHandler {
+    fn device_arrived(&mut self, device: Device<Context>) {
+        let _ = self.events_tx.send(UsbHotplugEvent::Arrived(device));
+    }
+
+    fn device_left(&mut self, device: Device<Context>) {
+        let _ = self.events_tx.send(UsbHotplugEvent::Left(device));
+    }
+}
+
 impl DevicesScannerTrait for UsbDevicesScanner {
     async fn start_scanning(
         &mut self,
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output=()> + Send>> + Send + Sync>,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        on_lost_device: OnLostDeviceCallback,
     ) {
         let (stop_scanning_tx, mut stop_scanning_rx) = mpsc::channel(SCANNING_CHANNEL_CAPACITY);
         self.stop_scanning_tx = Some(stop_scanning_tx);
 
+        match UsbDevicesScanner::_watch_hotplug_events(device_type, is_new_device.clone(), on_find_device.clone(), on_lost_device, &mut stop_scanning_rx).await {
+            Ok(_) => return,
+            Err(err) => warn!("USB hotplug is not available or stopped, falling back to polling: {}", err),
+        }
+
         loop {
             select! {
         _ = UsbDevicesScanner::_start_scanning(device_type, is_new_device.clone(), on_find_device.clone()) => {
@@ -58,9 +88,20 @@ impl UsbDevicesScanner {
     async fn _handle_device(
         device: Device<Context>,
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output=()> + Send>> + Send + Sync>,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
     ) -> Result<(), Box<dyn Error + Send>> {
+        if let Some(curr_device_serial) = Self::read_adb_device_serial(&device, device_type)? {
+            if is_new_device(curr_device_serial.clone()) {
+                let transport = UsbTransport::new(Some(device.clone()), Self::get_device_path(&device));
+                on_find_device(curr_device_serial, Box::new(transport), device_type).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_adb_device_serial(device: &Device<Context>, device_type: InterfaceType) -> Result<Option<String>, Box<dyn Error + Send>> {
         let (class_code, sub_class_code, protocol_code) = match device_type {
             InterfaceType::AndroidUsb(c, sc, p) | InterfaceType::AndroidTcp(c, sc, p) => (c, sc, p),
         };
@@ -81,26 +122,165 @@ impl UsbDevicesScanner {
             }
         });
 
-        if let Some(_) = matching_interface {
-            let device_handle = device.open()
-                .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?;
+        if matching_interface.is_none() {
+            return Ok(None);
+        }
 
-            let curr_device_serial = device_handle.read_serial_number_string_ascii(&device_desc)
-                .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?;
+        let device_handle = device.open()
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?;
 
-            if is_new_device(curr_device_serial.clone()) {
-                let transport = UsbTransport::new(Some(device.clone()));
-                on_find_device(curr_device_serial, Box::new(transport), device_type).await;
+        let curr_device_serial = device_handle.read_serial_number_string_ascii(&device_desc)
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?;
+
+        Ok(Some(curr_device_serial))
+    }
+
+    async fn _watch_hotplug_events(
+        device_type: InterfaceType,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        on_lost_device: OnLostDeviceCallback,
+        stop_scanning_rx: &mut mpsc::Receiver<()>,
+    ) -> Result<(), rusb::Error> {
+        let context = Context::new()?;
+        if !rusb::has_hotplug() {
+            return Err(rusb::Error::NotSupported);
+        }
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let registration = HotplugBuilder::new()
+            .enumerate(true)
+            .register::<Context, _>(&context, Box::new(UsbHotplugHandler { events_tx }))?;
+
+        let stop_events = Arc::new(AtomicBool::new(false));
+        let events_thread_stop = stop_events.clone();
+        thread::spawn(move || {
+            let _registration = registration;
+            while !events_thread_stop.load(Ordering::SeqCst) {
+                if let Err(err) = context.handle_events(Some(Duration::from_millis(HOTPLUG_EVENTS_TIMEOUT_MS))) {
+                    error!("Failed to handle USB hotplug events: {}", err);
+                    break;
+                }
+            }
+        });
+        info!("Watching USB hotplug events");
+
+        let attached_devices: Arc<AttachedUsbDevices> = Arc::new(DashMap::new());
+        let pending_devices: Arc<PendingUsbDevices> = Arc::new(DashMap::new());
+        let mut reconnect_interval = interval(Duration::from_millis(SCANNING_INTERVAL_MS));
+
+        loop {
+            select! {
+                hotplug_event = events_rx.recv() => {
+                    match hotplug_event {
+                        Some(UsbHotplugEvent::Arrived(device)) => {
+                            tokio::spawn(Self::_handle_arrived_device(device, device_type, attached_devices.clone(), pending_devices.clone(), is_new_device.clone(), on_find_device.clone(), false));
+                        }
+                        Some(UsbHotplugEvent::Left(device)) => {
+                            pending_devices.remove(&Self::get_device_key(&device));
+                            if let Some((_, (serial_number, _))) = attached_devices.remove(&Self::get_device_key(&device)) {
+                                tokio::spawn(on_lost_device(serial_number));
+                            }
+                        }
+                        None => {
+                            stop_events.store(true, Ordering::SeqCst);
+                            return Err(rusb::Error::Other);
+                        }
+                    }
+                }
+                _ = reconnect_interval.tick() => {
+                    Self::_reconnect_attached_devices(&attached_devices, device_type, &is_new_device, &on_find_device);
+                    Self::_retry_pending_devices(&pending_devices, device_type, &attached_devices, &is_new_device, &on_find_device);
+                }
+                _ = stop_scanning_rx.recv() => {
+                    break;
+                }
             }
         }
 
+        stop_events.store(true, Ordering::SeqCst);
         Ok(())
     }
 
+    async fn _handle_arrived_device(
+        device: Device<Context>,
+        device_type: InterfaceType,
+        attached_devices: Arc<AttachedUsbDevices>,
+        pending_devices: Arc<PendingUsbDevices>,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
+        is_retry: bool,
+    ) {
+        let serial_device = device.clone();
+        let read_result = tokio::task::spawn_blocking(move || {
+            Self::read_adb_device_serial(&serial_device, device_type)
+                .map_err(|err| (err.downcast_ref::<rusb::Error>() == Some(&rusb::Error::NoDevice), err.to_string()))
+        }).await;
+
+        let curr_device_serial = match read_result {
+            Ok(Ok(Some(curr_device_serial))) => curr_device_serial,
+            Ok(Ok(None)) => return,
+            Ok(Err((true, err))) => {
+                debug!("USB device left before its serial could be read: {}", err);
+                return;
+            }
+            Ok(Err((false, err))) => {
+                let (bus_number, address) = Self::get_device_key(&device);
+                if is_retry {
+                    debug!("Still unable to read serial of USB device {}:{}: {}", bus_number, address, err);
+                } else {
+                    warn!("Failed to read serial of USB device {}:{}, will retry: {}", bus_number, address, err);
+                }
+                pending_devices.insert(Self::get_device_key(&device), device);
+                return;
+            }
+            Err(err) => {
+                error!("Error handling device: {}", err);
+                return;
+            }
+        };
+
+        attached_devices.insert(Self::get_device_key(&device), (curr_device_serial.clone(), device.clone()));
+        if is_new_device(curr_device_serial.clone()) {
+            let transport = UsbTransport::new(Some(device.clone()), Self::get_device_path(&device));
+            on_find_device(curr_device_serial, Box::new(transport), device_type).await;
+        }
+    }
+
+    fn _reconnect_attached_devices(
+        attached_devices: &AttachedUsbDevices,
+        device_type: InterfaceType,
+        is_new_device: &IsNewDeviceCallback,
+        on_find_device: &OnFindDeviceCallback,
+    ) {
+        for attached_device in attached_devices.iter() {
+            let (serial_number, device) = attached_device.value();
+            if is_new_device(serial_number.clone()) {
+                let transport = UsbTransport::new(Some(device.clone()), Self::get_device_path(device));
+                tokio::spawn(on_find_device(serial_number.clone(), Box::new(transport), device_type));
+            }
+        }
+    }
+
+    fn _retry_pending_devices(
+        pending_devices: &Arc<PendingUsbDevices>,
+        device_type: InterfaceType,
+        attached_devices: &Arc<AttachedUsbDevices>,
+        is_new_device: &IsNewDeviceCallback,
+        on_find_device: &OnFindDeviceCallback,
+    ) {
+        let device_keys: Vec<(u8, u8)> = pending_devices.iter().map(|pending_device| *pending_device.key()).collect();
+        for device_key in device_keys {
+            if let Some((_, device)) = pending_devices.remove(&device_key) {
+                tokio::spawn(Self::_handle_arrived_device(device, device_type, attached_devices.clone(), pending_devices.clone(), is_new_device.clone(), on_find_device.clone(), true));
+            }
+        }
+    }
+
     async fn _start_scanning(
         device_type: InterfaceType,
-        is_new_device: Arc<dyn Fn(String) -> bool + Send + Sync>,
-        on_find_device: Arc<dyn Fn(String, Box<dyn Transport>, InterfaceType) -> Pin<Box<dyn Future<Output=()> + Send>> + Send + Sync>,
+        is_new_device: IsNewDeviceCallback,
+        on_find_device: OnFindDeviceCallback,
     ) {
         let mut handle_devices_tasks = vec![];
 
@@ -149,4 +329,18 @@ impl UsbDevicesScanner {
             }
         }
     }
+
+    fn get_device_key(device: &Device<Context>) -> (u8, u8) {
+        (device.bus_number(), device.address())
+    }
+
+    fn get_device_path(device: &Device<Context>) -> Option<String> {
+        let port_numbers = device.port_numbers().ok()?;
+        if port_numbers.is_empty() {
+            return None;
+        }
+
+        let ports = port_numbers.iter().map(|port| port.to_string()).collect::<Vec<_>>().join(".");
+        Some(format!("{}{}-{}", USB_DEVICE_PATH_PREFIX, device.bus_number(), ports))
+    }
 }
@@ -11,6 +11,10 @@ impl InterfaceType {
         InterfaceType::AndroidUsb(0xff, 0x42, 0x01)
     }
 
+    pub fn android_tcp() -> Self {
+        InterfaceType::AndroidTcp(0x00, 0x00, 0x00)
+    }
+
     pub fn is_android_usb_device(interface_type: InterfaceType) -> bool {
         match interface_type {
             InterfaceType::AndroidUsb(_, _, _) => true,
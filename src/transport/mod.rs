@@ -1,4 +1,5 @@
 pub mod enums;
 pub mod errors;
 pub mod transport;
-pub mod usb_transport;
\ No newline at end of file
+pub mod usb_transport;
+pub mod tcp_transport;
\ No newline at end of file
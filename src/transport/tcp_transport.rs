@@ -0,0 +1,146 @@
+use std::io::ErrorKind;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::Mutex;
+use tokio::time::{self, Instant};
+use crate::transport::enums::interface_type::InterfaceType;
+use crate::transport::errors::transport_error::TransportError;
+use crate::transport::errors::transport_error::TransportError::{CommunicationError, ConnectionError};
+use crate::transport::transport::Transport;
+
+#[derive(Debug)]
+pub struct TcpTransport {
+    address: String,
+    read_half: Option<Mutex<OwnedReadHalf>>,
+    write_half: Option<Mutex<OwnedWriteHalf>>,
+}
+
+impl TcpTransport {
+    pub const CONNECT_TIME_OUT: f64 = 5.0;
+    pub const WRITING_DEFAULT_TIME_OUT: f64 = 5.0;
+    const PARTIAL_READ_MIN_TIME_OUT: f64 = 1.0;
+
+    pub fn new(address: String) -> Self {
+        TcpTransport {
+            address,
+            read_half: None,
+            write_half: None,
+        }
+    }
+
+    async fn _acquire_device(&mut self, device_type: InterfaceType) -> Result<(), TransportError> {
+        if !InterfaceType::is_android_tcp_device(device_type) {
+            return Err(TransportError::UnexpectedError("Unsupported device type. Expected AndroidTcp interface.".to_string()));
+        }
+
+        let stream = time::timeout(Duration::from_secs_f64(Self::CONNECT_TIME_OUT), TcpStream::connect(&self.address))
+            .await
+            .map_err(|_| ConnectionError(format!("Failed to connect to {}: connection timed out", self.address)))?
+            .map_err(|e| ConnectionError(format!("Failed to connect to {}: {}", self.address, e)))?;
+        stream.set_nodelay(true).map_err(|e| CommunicationError(e.to_string()))?;
+
+        let (read_half, write_half) = stream.into_split();
+        self.read_half = Some(Mutex::new(read_half));
+        self.write_half = Some(Mutex::new(write_half));
+        Ok(())
+    }
+
+    async fn _bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
+        let mut read_half = self.read_half.as_ref().ok_or(TransportError::DeviceNotFound)?.lock().await;
+        let mut buffer = vec![0u8; length];
+
+        let deadline = Self::to_socket_timeout(transport_timeout_s).map(|read_timeout| Instant::now() + read_timeout);
+        let first_read = read_half.read(&mut buffer);
+        let bytes_read = match deadline {
+            Some(deadline) => time::timeout_at(deadline, first_read).await.map_err(|_| TransportError::Timeout)?,
+            None => first_read.await,
+        }.map_err(Self::map_io_error)?;
+
+        if bytes_read == 0 && length > 0 {
+            return Err(ConnectionError("Device connection lost".to_string()));
+        }
+
+        let remaining_read = read_half.read_exact(&mut buffer[bytes_read..]);
+        match deadline {
+            Some(deadline) => {
+                let remaining_deadline = deadline.max(Instant::now() + Duration::from_secs_f64(Self::PARTIAL_READ_MIN_TIME_OUT));
+                time::timeout_at(remaining_deadline, remaining_read).await
+                    .map_err(|_| CommunicationError(format!("Timed out after reading {} of {} bytes", bytes_read, length)))?
+            }
+            None => remaining_read.await,
+        }.map_err(Self::map_io_error)?;
+        Ok(buffer)
+    }
+
+    async fn _bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
+        let mut write_half = self.write_half.as_ref().ok_or(TransportError::DeviceNotFound)?.lock().await;
+        let write_timeout = Duration::from_secs_f64(transport_timeout_s.unwrap_or(Self::WRITING_DEFAULT_TIME_OUT));
+
+        time::timeout(write_timeout, write_half.write_all(data))
+            .await
+            .map_err(|_| TransportError::Timeout)?
+            .map_err(Self::map_io_error)?;
+        Ok(data.len())
+    }
+
+    async fn _verify_connection_status(&self) -> Result<(), TransportError> {
+        let write_half = self.write_half.as_ref().ok_or(TransportError::DeviceNotFound)?.lock().await;
+        write_half.peer_addr()
+            .map(|_| ())
+            .map_err(Self::map_io_error)
+    }
+
+    fn to_socket_timeout(timeout_s: f64) -> Option<Duration> {
+        if timeout_s > 0.0 {
+            Some(Duration::from_secs_f64(timeout_s))
+        } else {
+            None
+        }
+    }
+
+    fn map_io_error(err: std::io::Error) -> TransportError {
+        match err.kind() {
+            ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::BrokenPipe | ErrorKind::UnexpectedEof | ErrorKind::NotConnected => {
+                ConnectionError(format!("Device connection lost: {}", err))
+            }
+            _ => CommunicationError(err.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    async fn acquire_device(&mut self, device_type: InterfaceType) -> Result<(), TransportError> {
+        self._acquire_device(device_type).await
+    }
+
+    async fn release_device(&self) -> Result<(), TransportError> {
+        if let Some(write_half) = self.write_half.as_ref() {
+            return match write_half.lock().await.shutdown().await {
+                Ok(_) => Ok(()),
+                Err(err) if err.kind() == ErrorKind::NotConnected => Ok(()),
+                Err(err) => Err(ConnectionError(err.to_string())),
+            };
+        }
+        Ok(())
+    }
+
+    async fn bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
+        self._bulk_read(length, transport_timeout_s).await
+    }
+
+    async fn bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
+        self._bulk_write(data, transport_timeout_s).await
+    }
+
+    async fn verify_connection_status(&self) -> Result<(), TransportError> {
+        self._verify_connection_status().await
+    }
+
+    fn device_path(&self) -> Option<String> {
+        None
+    }
+}
@@ -1,4 +1,6 @@
+use std::sync::Arc;
 use std::time::Duration;
+use async_trait::async_trait;
 use rusb::{Context, Device, DeviceHandle, Direction, InterfaceDescriptor, TransferType};
 use crate::constants::{DEFAULT_BUFFER_SIZE};
 use crate::transport::transport::Transport;
@@ -10,18 +12,20 @@ use crate::transport::errors::transport_error::TransportError::CommunicationErro
 #[derive(Debug)]
 pub struct UsbTransport {
     device: Option<Device<Context>>,
-    device_handle: Option<DeviceHandle<Context>>,
+    device_handle: Option<Arc<DeviceHandle<Context>>>,
     interface_number: Option<u8>,
     bulk_in_endpoint: Option<u8>,
     bulk_out_endpoint: Option<u8>,
+    device_path: Option<String>,
 }
 
 impl UsbTransport {
     pub const WRITING_DEFAULT_TIME_OUT: f64 = 5.0;
 
-    pub fn new(device: Option<Device<Context>>) -> Self {
+    pub fn new(device: Option<Device<Context>>, device_path: Option<String>) -> Self {
         UsbTransport {
             device,
+            device_path,
             device_handle: None,
             bulk_in_endpoint: None,
             bulk_out_endpoint: None,
@@ -61,7 +65,7 @@ impl UsbTransport {
                     }
 
                     self.setup_device(interface_desc)?;
-                    self.device_handle = Some(device_handle);
+                    self.device_handle = Some(Arc::new(device_handle));
                     self.interface_number = Some(interface.number());
 
                     return Ok(());
@@ -71,58 +75,63 @@ impl UsbTransport {
         Err(TransportError::UnexpectedError("An unexpected error occurred while connecting to the device".to_string()))
     }
 
-    fn _bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
-        match (self.device_handle.as_ref(), self.bulk_in_endpoint) {
+    async fn _bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
+        match (self.device_handle.clone(), self.bulk_in_endpoint) {
             (Some(device_handle), Some(endpoint)) => {
-                let mut buffer = Vec::with_capacity(length);
-
-                while buffer.len() < length {
-                    let remaining = length - buffer.len();
-                    let chunk_size = remaining.min(DEFAULT_BUFFER_SIZE);
-                    let mut chunk = vec![0u8; chunk_size];
-
-                    match device_handle.read_bulk(endpoint, &mut chunk, Duration::from_secs_f64(transport_timeout_s)) {
-                        Ok(bytes_read) => {
-                            if bytes_read == 0 {
-                                return Err(TransportError::ConnectionError(
-                                    "Device connection lost".to_string()
-                                ));
-                            }
-                            chunk.truncate(bytes_read);
-                            buffer.extend_from_slice(&chunk);
-                        }
-                        Err(UsbError::Timeout) => return Err(TransportError::Timeout),
-                        Err(UsbError::NoDevice) => return Err(TransportError::DeviceNotFound),
-                        Err(UsbError::Pipe) => return Err(TransportError::ConnectionError("USB pipe error".to_string())),
-                        Err(UsbError::Access) => return Err(TransportError::Unauthorized("No permission to access USB device".to_string())),
-                        Err(UsbError::Overflow) => return Err(TransportError::ConnectionError("USB buffer overflow".to_string())),
-                        Err(UsbError::Io) => return Err(TransportError::ConnectionError("USB I/O error".to_string())),
-                        Err(UsbError::InvalidParam) => return Err(TransportError::ConnectionError("Invalid USB parameters".to_string())),
-                        Err(UsbError::Busy) =>return Err(TransportError::ConnectionError("USB device/endpoint busy".to_string())),
-                        Err(UsbError::Other) => return Err(TransportError::ConnectionError("Platform-specific USB error".to_string())),
-                        Err(err) => return Err(CommunicationError(format!("Unexpected error: {}", err))),
-                    }
-                }
-
-                if buffer.len() > length {
-                    buffer.truncate(length);
-                }
-
-                Ok(buffer)
+                Self::run_blocking(move || Self::read_bulk(&device_handle, endpoint, length, transport_timeout_s)).await
             }
             (None, _) => Err(TransportError::DeviceNotFound),
             (_, None) => Err(CommunicationError("USB endpoint not configured".to_string())),
         }
     }
 
-    fn _bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
+    fn read_bulk(device_handle: &DeviceHandle<Context>, endpoint: u8, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
+        let mut buffer = Vec::with_capacity(length);
+
+        while buffer.len() < length {
+            let remaining = length - buffer.len();
+            let chunk_size = remaining.min(DEFAULT_BUFFER_SIZE);
+            let mut chunk = vec![0u8; chunk_size];
+
+            match device_handle.read_bulk(endpoint, &mut chunk, Duration::from_secs_f64(transport_timeout_s)) {
+                Ok(bytes_read) => {
+                    if bytes_read == 0 {
+                        return Err(TransportError::ConnectionError(
+                            "Device connection lost".to_string()
+                        ));
+                    }
+                    chunk.truncate(bytes_read);
+                    buffer.extend_from_slice(&chunk);
+                }
+                Err(UsbError::Timeout) => return Err(TransportError::Timeout),
+                Err(UsbError::NoDevice) => return Err(TransportError::DeviceNotFound),
+                Err(UsbError::Pipe) => return Err(TransportError::ConnectionError("USB pipe error".to_string())),
+                Err(UsbError::Access) => return Err(TransportError::Unauthorized("No permission to access USB device".to_string())),
+                Err(UsbError::Overflow) => return Err(TransportError::ConnectionError("USB buffer overflow".to_string())),
+                Err(UsbError::Io) => return Err(TransportError::ConnectionError("USB I/O error".to_string())),
+                Err(UsbError::InvalidParam) => return Err(TransportError::ConnectionError("Invalid USB parameters".to_string())),
+                Err(UsbError::Busy) =>return Err(TransportError::ConnectionError("USB device/endpoint busy".to_string())),
+                Err(UsbError::Other) => return Err(TransportError::ConnectionError("Platform-specific USB error".to_string())),
+                Err(err) => return Err(CommunicationError(format!("Unexpected error: {}", err))),
+            }
+        }
+
+        if buffer.len() > length {
+            buffer.truncate(length);
+        }
+
+        Ok(buffer)
+    }
+
+    async fn _bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
         let timeout = transport_timeout_s
             .map(Duration::from_secs_f64)
             .unwrap_or_else(|| Duration::from_secs_f64(Self::WRITING_DEFAULT_TIME_OUT));
 
-        match (self.device_handle.as_ref(), self.bulk_out_endpoint) {
+        match (self.device_handle.clone(), self.bulk_out_endpoint) {
             (Some(device_handle), Some(endpoint)) => {
-                device_handle.write_bulk(endpoint, data, timeout)
+                let data = data.to_vec();
+                Self::run_blocking(move || device_handle.write_bulk(endpoint, &data, timeout)
                     .map_err(|e| match e {
                         rusb::Error::Timeout => TransportError::Timeout,
                         rusb::Error::NoDevice => TransportError::DeviceNotFound,
@@ -131,7 +140,7 @@ impl UsbTransport {
                         rusb::Error::Access => TransportError::Unauthorized("No permission to access USB device".to_string()),
                         rusb::Error::InvalidParam => TransportError::UnexpectedError("Invalid USB parameter".to_string()),
                         _ => CommunicationError(format!("USB error: {}", e))
-                    })
+                    })).await
             }
             (None, _) => {
                 Err(TransportError::DeviceNotFound)
@@ -155,33 +164,47 @@ impl UsbTransport {
         Ok(())
     }
 
-    pub fn _verify_connection_status(&self) -> Result<(), TransportError> {
-        match (self.device_handle.as_ref(), self.interface_number) {
+    async fn _verify_connection_status(&self) -> Result<(), TransportError> {
+        match (self.device_handle.clone(), self.interface_number) {
             (Some(device_handle), Some(interface_number)) => {
-                if device_handle.active_configuration().is_err() {
-                    return Err(TransportError::ConnectionError("Device handle is no longer active".to_string()));
-                }
-                if device_handle.kernel_driver_active(interface_number).is_err() {
-                    return Err(TransportError::ConnectionError("Interface is no longer claimed".to_string()));
-                }
-                Ok(())
+                Self::run_blocking(move || {
+                    if device_handle.active_configuration().is_err() {
+                        return Err(TransportError::ConnectionError("Device handle is no longer active".to_string()));
+                    }
+                    if device_handle.kernel_driver_active(interface_number).is_err() {
+                        return Err(TransportError::ConnectionError("Interface is no longer claimed".to_string()));
+                    }
+                    Ok(())
+                }).await
             }
             _ => {
                 Err(TransportError::UnexpectedError("Device handle or interface number not set correctly".to_string()))
             }
         }
     }
+
+    async fn run_blocking<T: Send + 'static>(usb_operation: impl FnOnce() -> Result<T, TransportError> + Send + 'static) -> Result<T, TransportError> {
+        tokio::task::spawn_blocking(usb_operation)
+            .await
+            .map_err(|err| TransportError::UnexpectedError(format!("USB I/O task failed: {}", err)))?
+    }
 }
 
+#[async_trait]
 impl Transport for UsbTransport {
-    fn acquire_device(&mut self, device_type: InterfaceType) -> Result<(), TransportError> {
-        self._acquire_device(device_type)
+    async fn acquire_device(&mut self, device_type: InterfaceType) -> Result<(), TransportError> {
+        let mut usb_transport = UsbTransport::new(self.device.clone(), self.device_path.clone());
+        *self = Self::run_blocking(move || {
+            usb_transport._acquire_device(device_type)?;
+            Ok(usb_transport)
+        }).await?;
+        Ok(())
     }
 
-    fn release_device(&self) -> Result<(), TransportError> {
-        if let Some(ref handle) = self.device_handle {
+    async fn release_device(&self) -> Result<(), TransportError> {
+        if let Some(handle) = self.device_handle.clone() {
             if let Some(if_num) = self.interface_number {
-                return match handle.release_interface(if_num) {
+                return match Self::run_blocking(move || Ok(handle.release_interface(if_num))).await? {
                     Ok(_) => Ok(()),
                     Err(rusb::Error::NotFound) => Err(TransportError::ConnectionError("Device not found".to_string())),
                     Err(rusb::Error::NoDevice) => Err(TransportError::ConnectionError("No such device".to_string())),
@@ -194,16 +217,20 @@ impl Transport for UsbTransport {
         Ok(())
     }
 
-    fn bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
-        self._bulk_read(length, transport_timeout_s)
+    async fn bulk_read(&self, length: usize, transport_timeout_s: f64) -> Result<Vec<u8>, TransportError> {
+        self._bulk_read(length, transport_timeout_s).await
     }
 
 
-    fn bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
-        self._bulk_write(data, transport_timeout_s)
+    async fn bulk_write(&self, data: &[u8], transport_timeout_s: Option<f64>) -> Result<usize, TransportError> {
+        self._bulk_write(data, transport_timeout_s).await
+    }
+
+    async fn verify_connection_status(&self) -> Result<(), TransportError> {
+        self._verify_connection_status().await
     }
 
-    fn verify_connection_status(&self) -> Result<(), TransportError> {
-        self._verify_connection_status()
+    fn device_path(&self) -> Option<String> {
+        self.device_path.clone()
     }
 }
\ No newline at end of file
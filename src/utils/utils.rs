@@ -1,16 +1,29 @@
+use std::env;
 use std::error::Error;
-use std::fs::read_to_string;
+use std::fs::{create_dir_all, read_dir, read_to_string, write, OpenOptions};
+use std::io::Write;
 use std::net::TcpListener;
+use std::path::Path;
 use std::str;
 
-use rsa::{PaddingScheme, pkcs8::DecodePrivateKey, RsaPrivateKey};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rand::rngs::OsRng;
+use rsa::{BigUint, PaddingScheme, PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
 
-use crate::constants::{FAIL, NULL_TERMINATOR, OKAY};
+use crate::constants::{ADB_PRIVATE_KEY_FILE, ADB_PUBLIC_KEY_FILE, ADB_RSA_KEY_BITS, ADB_VENDOR_KEY_EXTENSION, ADB_VENDOR_KEYS_ENV, FAIL, NULL_TERMINATOR, OKAY};
 
-pub fn sign_data(private_key_path: &str, data: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+pub fn load_private_key(private_key_path: &str) -> Result<RsaPrivateKey, Box<dyn Error>> {
     let keys_as_string = read_to_string(private_key_path)?;
-    let private_key = RsaPrivateKey::from_pkcs8_pem(&keys_as_string).unwrap();
+    let private_key = RsaPrivateKey::from_pkcs8_pem(&keys_as_string)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(&keys_as_string))
+        .map_err(|err| format!("{} is not a valid PKCS#8 or PKCS#1 RSA private key: {}", private_key_path, err))?;
+    Ok(private_key)
+}
 
+pub fn sign_data(private_key: &RsaPrivateKey, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
     let mut data_to_sign = vec![0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14];
     data_to_sign.extend_from_slice(data);
     let padding = PaddingScheme::new_pkcs1v15_sign(None);
@@ -19,6 +32,98 @@ pub fn sign_data(private_key_path: &str, data: &[u8]) -> Result<Vec<u8>, Box<dyn
     Ok(signature)
 }
 
+pub fn get_adb_vendor_key_paths() -> Vec<String> {
+    let Some(vendor_keys) = env::var_os(ADB_VENDOR_KEYS_ENV) else {
+        return Vec::new();
+    };
+
+    let mut key_paths = Vec::new();
+    for vendor_key_path in env::split_paths(&vendor_keys).filter(|path| !path.as_os_str().is_empty()) {
+        if !vendor_key_path.is_dir() {
+            key_paths.push(vendor_key_path.to_string_lossy().to_string());
+            continue;
+        }
+
+        let Ok(dir_entries) = read_dir(&vendor_key_path) else {
+            continue;
+        };
+        let mut dir_key_paths: Vec<String> = dir_entries
+            .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == ADB_VENDOR_KEY_EXTENSION))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        dir_key_paths.sort();
+        key_paths.extend(dir_key_paths);
+    }
+    key_paths
+}
+
+pub fn ensure_adb_key_pair(adb_keys_path: &str) -> Result<bool, Box<dyn Error>> {
+    let private_key_path = Path::new(adb_keys_path).join(ADB_PRIVATE_KEY_FILE);
+    let public_key_path = Path::new(adb_keys_path).join(ADB_PUBLIC_KEY_FILE);
+
+    if private_key_path.exists() {
+        if !public_key_path.exists() {
+            let private_key = load_private_key(&private_key_path.to_string_lossy())?;
+            write(&public_key_path, encode_android_public_key(&RsaPublicKey::from(&private_key))?)?;
+        }
+        return Ok(false);
+    }
+
+    create_dir_all(adb_keys_path)?;
+    let private_key = RsaPrivateKey::new(&mut OsRng, ADB_RSA_KEY_BITS)?;
+    let private_key_pem = private_key.to_pkcs8_pem(LineEnding::LF)?;
+
+    let mut private_key_options = OpenOptions::new();
+    private_key_options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut private_key_options, 0o600);
+    private_key_options.open(&private_key_path)?.write_all(private_key_pem.as_bytes())?;
+
+    write(&public_key_path, encode_android_public_key(&RsaPublicKey::from(&private_key))?)?;
+    Ok(true)
+}
+
+fn encode_android_public_key(public_key: &RsaPublicKey) -> Result<String, Box<dyn Error>> {
+    let modulus_size = ADB_RSA_KEY_BITS / 8;
+    let modulus = public_key.n();
+    if modulus.bits() != ADB_RSA_KEY_BITS {
+        return Err(format!("ADB requires a {}-bit RSA key, found {} bits", ADB_RSA_KEY_BITS, modulus.bits()).into());
+    }
+    let exponent = public_key.e().to_bytes_le();
+    if exponent.len() > 4 {
+        return Err("RSA public exponent does not fit in 32 bits".into());
+    }
+
+    let modulus_bytes = modulus.to_bytes_le();
+    let modulus_low_word = u32::from_le_bytes([modulus_bytes[0], modulus_bytes[1], modulus_bytes[2], modulus_bytes[3]]);
+    let mut modulus_inverse = modulus_low_word;
+    for _ in 0..4 {
+        modulus_inverse = modulus_inverse.wrapping_mul(2u32.wrapping_sub(modulus_low_word.wrapping_mul(modulus_inverse)));
+    }
+    let r_squared = (BigUint::from(1u32) << (2 * ADB_RSA_KEY_BITS)) % modulus;
+
+    let mut encoded_key = Vec::with_capacity(8 + 2 * modulus_size + 4);
+    encoded_key.extend_from_slice(&((modulus_size / 4) as u32).to_le_bytes());
+    encoded_key.extend_from_slice(&modulus_inverse.wrapping_neg().to_le_bytes());
+    for value in [modulus_bytes, r_squared.to_bytes_le()] {
+        encoded_key.extend_from_slice(&value);
+        encoded_key.resize(encoded_key.len() + modulus_size - value.len(), 0);
+    }
+    encoded_key.extend_from_slice(&exponent);
+    encoded_key.resize(encoded_key.len() + 4 - exponent.len(), 0);
+
+    Ok(format!("{} {}", STANDARD.encode(encoded_key), adb_key_owner()))
+}
+
+fn adb_key_owner() -> String {
+    let user = env::var("USER").or_else(|_| env::var("USERNAME")).unwrap_or_else(|_| String::from("unknown"));
+    let host = hostname::get().ok()
+        .and_then(|host| host.into_string().ok())
+        .unwrap_or_else(|| String::from("unknown"));
+    format!("{}@{}", user, host)
+}
+
 pub fn get_adb_key_path() -> Result<String, Box<dyn Error>> {
     let mut path = dirs::home_dir().ok_or("Home directory not found")?;
 
@@ -59,4 +164,8 @@ pub fn fail() -> String {
 
 pub fn okay() -> String {
     String::from(OKAY)
-}
\ No newline at end of file
+}
+
+pub(crate) fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}